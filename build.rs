@@ -0,0 +1,8 @@
+use std::env;
+
+//`TARGET` and `HOST` are set only for the build script, the C test of `tests/ffi.rs` compiles for them
+fn main() {
+	println!("cargo:rustc-env=LAMANSH_TARGET={}", env::var("TARGET").unwrap());
+	println!("cargo:rustc-env=LAMANSH_HOST={}", env::var("HOST").unwrap());
+	println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,296 @@
+/*!
+Derive `ToLamansh` and `FromLamansh` for structures stored as positional Lamansh elements.
+
+# Use
+```rust
+extern crate cluLamansh;
+#[macro_use]
+extern crate cluLamansh_derive;
+
+use cluLamansh::lamansh::record::ToLamanshRecord;
+use cluLamansh::lamansh::record::FromLamanshRecord;
+
+#[derive(ToLamansh, FromLamansh, Debug, PartialEq)]
+#[lamansh(count = "U8", value = "U16")]
+struct Message<'a> {
+	id: u32,
+	name: String,
+
+	#[lamansh(index = 3)]
+	data: &'a [u8],
+
+	#[lamansh(skip)]
+	cached: usize,
+}
+
+pub fn main() {
+	let array = Message { id: 1, name: "test".to_string(), data: b"data", cached: 10 }.to_lamansh_record().unwrap();
+	let message = Message::from_lamansh_array(&array).unwrap();
+
+	assert_eq!(message, Message { id: 1, name: "test".to_string(), data: b"data", cached: 0 });
+}
+```
+
+# Attributes
+* `#[lamansh(count = "U8", value = "U64")]` on the structure - the profile of the protocol, by default `U8`, `U64`.
+* `#[lamansh(index = 3)]` on the field - the index of the element, the following fields continue from it. Skipped indexes are written as empty elements.
+* `#[lamansh(skip)]` on the field - the field is not written, `Default::default()` is used when reading.
+*/
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate syn;
+#[macro_use]
+extern crate quote;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::DeriveInput;
+use syn::Data;
+use syn::Fields;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Lit;
+use syn::Type;
+use syn::Member;
+use syn::Index;
+use syn::Attribute;
+use syn::Lifetime;
+use syn::LifetimeDef;
+use syn::GenericParam;
+
+
+#[proc_macro_derive(ToLamansh, attributes(lamansh))]
+pub fn to_lamansh(input: TokenStream) -> TokenStream {
+	let input: DeriveInput = syn::parse(input).expect("ToLamansh: invalid input");
+	let record = Record::new(&input);
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let (nc, n) = (&record.count_sized, &record.value_sized);
+
+	let values = record.elements().into_iter().map(|field| match field {
+		Some(field) => {
+			let member = &field.member;
+			quote! { ::cluLamansh::lamansh::record::ToLamanshField::to_lamansh_field(&self.#member) }
+		},
+		None => quote! { ::std::borrow::Cow::Borrowed(&[][..]) },
+	});
+	let types = record.fields.iter().filter(|a| a.index.is_some()).map(|field| &field.ty);
+	let where_types = where_clause.map(|a| a.predicates.iter().collect::<Vec<_>>()).unwrap_or_default();
+
+	let expanded = quote! {
+		impl #impl_generics ::cluLamansh::lamansh::record::ToLamanshRecord for #name #ty_generics
+			where #(#where_types,)* #(#types: ::cluLamansh::lamansh::record::ToLamanshField,)* {
+
+			type CountSized = #nc;
+			type ValueSized = #n;
+
+			fn update_buffer_record(&self, vec_result: &mut ::cluLamansh::lamansh::buffer::LamanshBuffer) -> ::std::result::Result<(), ::cluLamansh::lamansh::build::ToLamanshErr> {
+				let values: ::std::vec::Vec<::std::borrow::Cow<[u8]>> = vec![#(#values),*];
+				let array: ::std::vec::Vec<&[u8]> = values.iter().map(|a| &**a).collect();
+
+				::cluLamansh::lamansh::build::ToLamansh::update_buffer::<#nc, #n>(&array, vec_result)
+			}
+		}
+	};
+
+	expanded.into()
+}
+
+#[proc_macro_derive(FromLamansh, attributes(lamansh))]
+pub fn from_lamansh(input: TokenStream) -> TokenStream {
+	let input: DeriveInput = syn::parse(input).expect("FromLamansh: invalid input");
+	let record = Record::new(&input);
+
+	let name = &input.ident;
+	let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+	let (nc, n) = (&record.count_sized, &record.value_sized);
+
+	//The lifetime of the borrowed fields, or a new one when the structure has no lifetimes
+	let mut generics = input.generics.clone();
+	let first_lifetime = generics.lifetimes().next().map(|a| a.lifetime.clone());
+	let lifetime = match first_lifetime {
+		Some(a) => a,
+		None => {
+			let lifetime = Lifetime::new("'__lamansh", Span::call_site());
+			generics.params.insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+
+			lifetime
+		},
+	};
+	let (impl_generics, _, _) = generics.split_for_impl();
+
+	let count = record.len();
+	let init = record.fields.iter().map(|field| {
+		let member = &field.member;
+		match field.index {
+			Some(index) => quote! { #member: ::cluLamansh::lamansh::record::FromLamanshField::from_lamansh_field(values[#index])? },
+			None => quote! { #member: ::std::default::Default::default() },
+		}
+	});
+	let types = record.fields.iter().filter(|a| a.index.is_some()).map(|field| {
+		let ty = &field.ty;
+		quote! { #ty: ::cluLamansh::lamansh::record::FromLamanshField<#lifetime> }
+	});
+	let where_types = where_clause.map(|a| a.predicates.iter().collect::<Vec<_>>()).unwrap_or_default();
+
+	let expanded = quote! {
+		impl #impl_generics ::cluLamansh::lamansh::record::FromLamanshRecord<#lifetime> for #name #ty_generics
+			where #(#where_types,)* #(#types,)* {
+
+			type CountSized = #nc;
+			type ValueSized = #n;
+
+			#[allow(unused_mut)]
+			fn from_lamansh(lamansh: &::cluLamansh::lamansh::cluLamansh<#lifetime, #nc, #n>) -> ::std::result::Result<Self, ::cluLamansh::lamansh::cluLamanshErr> {
+				let mut values: [&#lifetime [u8]; #count] = [&[]; #count];
+				let mut len = 0;
+				for value in lamansh.clone().into_iter().take(#count) {
+					values[len] = value?;
+					len += 1;
+				}
+				if len < #count {
+					return Err( ::cluLamansh::lamansh::cluLamanshErr::ErrRecordLen );
+				}
+
+				Ok( #name { #(#init),* } )
+			}
+		}
+	};
+
+	expanded.into()
+}
+
+
+
+struct Record {
+	count_sized: Type,
+	value_sized: Type,
+
+	fields: Vec<Field>,
+}
+
+struct Field {
+	member: Member,
+	ty: Type,
+
+	///`None` - skipped field
+	index: Option<usize>,
+}
+
+impl Record {
+	fn new(input: &DeriveInput) -> Self {
+		let fields = match input.data {
+			Data::Struct(ref a) => &a.fields,
+			_ => panic!("Lamansh: only structures can be records"),
+		};
+
+		let mut count_sized = sized_type("LamanshNameCountSized");
+		let mut value_sized = sized_type("LamanshValueSized");
+		for meta in lamansh_attrs(&input.attrs) {
+			match meta {
+				Meta::NameValue(ref a) if a.ident == "count" => count_sized = sized_type(&lit_str(&a.lit)),
+				Meta::NameValue(ref a) if a.ident == "value" => value_sized = sized_type(&lit_str(&a.lit)),
+				_ => panic!("Lamansh: unknown structure attribute, expected `count = \"..\"` or `value = \"..\"`"),
+			}
+		}
+
+		let mut next_index = 0;
+		let mut record_fields = Vec::new();
+		let iter = match *fields {
+			Fields::Named(ref a) => a.named.iter().collect::<Vec<_>>(),
+			Fields::Unnamed(ref a) => a.unnamed.iter().collect::<Vec<_>>(),
+			Fields::Unit => Vec::new(),
+		};
+		for (n, field) in iter.into_iter().enumerate() {
+			let member = match field.ident {
+				Some(ref a) => Member::Named(a.clone()),
+				None => Member::Unnamed(Index::from(n)),
+			};
+
+			let mut skip = false;
+			let mut index = None;
+			for meta in lamansh_attrs(&field.attrs) {
+				match meta {
+					Meta::Word(ref a) if a == "skip" => skip = true,
+					Meta::NameValue(ref a) if a.ident == "index" => index = match a.lit {
+						Lit::Int(ref a) => Some(a.value() as usize),
+						_ => panic!("Lamansh: `index` must be an integer"),
+					},
+					_ => panic!("Lamansh: unknown field attribute, expected `skip` or `index = ..`"),
+				}
+			}
+
+			let index = match (skip, index) {
+				(true, _) => None,
+				(false, Some(a)) => Some(a),
+				(false, None) => Some(next_index),
+			};
+			if let Some(index) = index {
+				if record_fields.iter().any(|a: &Field| a.index == Some(index)) {
+					panic!("Lamansh: element index {} is used by several fields", index);
+				}
+				next_index = index + 1;
+			}
+
+			record_fields.push(Field {
+				member: member,
+				ty: field.ty.clone(),
+				index: index,
+			});
+		}
+
+		Record {
+			count_sized: count_sized,
+			value_sized: value_sized,
+
+			fields: record_fields,
+		}
+	}
+
+	///Number of elements in the record
+	fn len(&self) -> usize {
+		self.fields.iter().filter_map(|a| a.index).max().map(|a| a + 1).unwrap_or(0)
+	}
+
+	///Fields in the order of the elements, `None` - an empty element in place of a skipped index
+	fn elements(&self) -> Vec<Option<&Field>> {
+		(0 .. self.len()).map(|index| self.fields.iter().find(|a| a.index == Some(index))).collect()
+	}
+}
+
+fn lamansh_attrs(attrs: &[Attribute]) -> Vec<Meta> {
+	let mut result = Vec::new();
+	for attr in attrs {
+		match attr.interpret_meta() {
+			Some(Meta::List(ref list)) if list.ident == "lamansh" => {
+				for nested in list.nested.iter() {
+					match *nested {
+						NestedMeta::Meta(ref a) => result.push(a.clone()),
+						_ => panic!("Lamansh: invalid attribute"),
+					}
+				}
+			},
+			_ => {},
+		}
+	}
+	result
+}
+
+fn lit_str(lit: &Lit) -> String {
+	match *lit {
+		Lit::Str(ref a) => a.value(),
+		_ => panic!("Lamansh: expected a string"),
+	}
+}
+
+///Short names of the types are taken from `lamansh::sized`, the rest are parsed as a path.
+fn sized_type(name: &str) -> Type {
+	let path = match name {
+		"U8" | "U16" | "U24" | "U32" | "U64" |
+		"LamanshNameCountSized" | "LamanshValueSized" => format!("::cluLamansh::lamansh::sized::{}", name),
+		_ => name.to_string(),
+	};
+	syn::parse_str(&path).expect("Lamansh: invalid sized type")
+}
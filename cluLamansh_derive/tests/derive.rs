@@ -0,0 +1,70 @@
+extern crate cluLamansh;
+#[macro_use]
+extern crate cluLamansh_derive;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+use cluLamansh::lamansh::sized::U64;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::cluLamanshErr;
+use cluLamansh::lamansh::record::ToLamanshRecord;
+use cluLamansh::lamansh::record::FromLamanshRecord;
+
+#[derive(ToLamansh, FromLamansh, Debug, PartialEq)]
+#[lamansh(count = "U8", value = "U16")]
+struct Message<'a> {
+	id: u32,
+	name: String,
+
+	#[lamansh(index = 3)]
+	data: &'a [u8],
+	flag: bool,
+
+	#[lamansh(skip)]
+	cached: usize,
+}
+
+#[derive(ToLamansh, FromLamansh, Debug, PartialEq)]
+struct Pair(i64, Vec<u8>);
+
+#[test]
+fn record_layout() {
+	let message = Message { id: 258, name: "test".to_string(), data: b"data", flag: true, cached: 10 };
+
+	assert_eq!(
+		message.to_lamansh_record(),
+
+		[
+			&[0u8, 0, 1, 2][..],
+			&b"test"[..],
+			&b""[..],
+			&b"data"[..],
+			&[1u8][..],
+		].to_lamansh::<U8, U16>()
+	);
+}
+
+#[test]
+fn record_round_trip() {
+	let array = Message { id: 7, name: "name".to_string(), data: b"\x00\xFF", flag: false, cached: 10 }.to_lamansh_record().unwrap();
+
+	assert_eq!(
+		Message::from_lamansh_array(&array),
+
+		Ok( Message { id: 7, name: "name".to_string(), data: b"\x00\xFF", flag: false, cached: 0 } )
+	);
+
+	let array = Pair(-1, b"value".to_vec()).to_lamansh_record().unwrap();
+	let lamansh = new_custom_lamansh::<U8, U64>(&array).unwrap();
+	assert_eq!(Pair::from_lamansh(&lamansh), Ok( Pair(-1, b"value".to_vec()) ));
+}
+
+#[test]
+fn record_errors() {
+	let array = [&[0u8, 0, 0, 1][..], &b"name"[..]].to_lamansh::<U8, U16>().unwrap();
+	assert_eq!(Message::from_lamansh_array(&array), Err( cluLamanshErr::ErrRecordLen ));
+
+	let array = [&[0u8, 1][..], &b"name"[..], &b""[..], &b""[..], &[1u8][..]].to_lamansh::<U8, U16>().unwrap();
+	assert_eq!(Message::from_lamansh_array(&array), Err( cluLamanshErr::ErrFieldValue ));
+}
@@ -0,0 +1,78 @@
+/*
+ * C ABI of cluLamansh, the widths of the count and the lengths are given in bytes (1, 2, 3, 4 or 8).
+ * Functions return LAMANSH_OK or an error code.
+ */
+
+#ifndef CLU_LAMANSH_H
+#define CLU_LAMANSH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define LAMANSH_OK 0
+
+/* cluLamanshErr */
+#define LAMANSH_ERR_EMPTY_LAMANSH 1
+#define LAMANSH_ERR_GET_SIZE_ARRAY 2
+#define LAMANSH_ERR_GET_VALUE_HEAD 3
+#define LAMANSH_ERR_GET_VALUE 4
+#define LAMANSH_ERR_GET_NEXT_VALUE 5
+#define LAMANSH_ERR_RECORD_LEN 6
+#define LAMANSH_ERR_FIELD_VALUE 7
+#define LAMANSH_ERR_GET_ENVELOPE 8
+#define LAMANSH_ERR_ENVELOPE_MAGIC 9
+#define LAMANSH_ERR_ENVELOPE_VERSION 10
+#define LAMANSH_ERR_ENVELOPE_PROFILE 11
+#define LAMANSH_ERR_ENVELOPE_FLAGS 12
+#define LAMANSH_ERR_GET_CHECKSUM 13
+#define LAMANSH_ERR_GET_INDEX 14
+#define LAMANSH_ERR_OVERWRITE_LEN 15
+#define LAMANSH_ERR_CHECKSUM_MISMATCH 16
+#define LAMANSH_ERR_CODEC 17
+#define LAMANSH_ERR_GET_TAG 18
+#define LAMANSH_ERR_AUTH_TAG 19
+#define LAMANSH_ERR_DEDUP_REF 20
+#define LAMANSH_ERR_DELTA_BASE 21
+#define LAMANSH_ERR_DELTA_PATCH 22
+
+/* ToLamanshErr */
+#define LAMANSH_ERR_EMPTY_ARRAY 101
+#define LAMANSH_ERR_COUNT_OVERFLOW 102
+#define LAMANSH_ERR_VALUE_OVERFLOW 103
+#define LAMANSH_ERR_INDEX_OVERFLOW 104
+#define LAMANSH_ERR_INVALID_LAMANSH 105
+
+/* Null pointer in the arguments */
+#define LAMANSH_ERR_NULL 201
+/* Unsupported width */
+#define LAMANSH_ERR_WIDTH 202
+
+/* Parsed protocol, the data is not copied and must live longer than the handle */
+typedef struct LamanshHandle LamanshHandle;
+
+/* Parse len bytes of data, the handle is freed with lamansh_free */
+int32_t lamansh_parse(const uint8_t *data, size_t len, uint8_t count_width, uint8_t len_width, LamanshHandle **out);
+
+void lamansh_free(LamanshHandle *handle);
+
+/* Number of elements, 0 for the null handle */
+size_t lamansh_count(const LamanshHandle *handle);
+
+/* Pointer to the value of the element in the parsed data and its len */
+int32_t lamansh_get(const LamanshHandle *handle, size_t index, const uint8_t **out_data, size_t *out_len);
+
+/* Build the protocol from count values, the buffer is freed with lamansh_buffer_free */
+int32_t lamansh_build(uint8_t count_width, uint8_t len_width, const uint8_t *const *data, const size_t *lens, size_t count, uint8_t **out_data, size_t *out_len);
+
+/* Free the buffer of lamansh_build */
+void lamansh_buffer_free(uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,414 @@
+/*!
+Encoding, decoding and inspecting Lamansh files.
+
+```text
+lamansh encode [OPTIONS] [-o OUT] [--files FILE...] [VALUE...]
+lamansh decode [OPTIONS] [--hex | --out-dir DIR] [INPUT]
+lamansh inspect [OPTIONS] [INPUT]
+lamansh validate [OPTIONS] [INPUT]
+lamansh transcode [OPTIONS] --to-count-width W --to-len-width W [-o OUT] [INPUT]
+
+OPTIONS: --count-width W, --len-width W (W: u8, u16, u24, u32, u64 or 1, 2, 3, 4, 8; default u8 and u64)
+```
+
+`encode` without values reads the values from the lines of stdin, `INPUT` by default is stdin.
+ */
+
+extern crate cluLamansh;
+
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::sized::LamanshSized;
+use cluLamansh::lamansh::sized::LamanshWidth;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+use cluLamansh::lamansh::sized::U24;
+use cluLamansh::lamansh::sized::U32;
+use cluLamansh::lamansh::sized::U64;
+use cluLamansh::lamansh::stream::message_len;
+use cluLamansh::lamansh::transcode::transcode;
+use cluLamansh::lamansh::cluLamansh as Lamansh;
+use cluLamansh::new_custom_lamansh;
+
+const USAGE: &str = "\
+usage: lamansh encode [OPTIONS] [-o OUT] [--files FILE...] [VALUE...]
+       lamansh decode [OPTIONS] [--hex | --out-dir DIR] [INPUT]
+       lamansh inspect [OPTIONS] [INPUT]
+       lamansh validate [OPTIONS] [INPUT]
+       lamansh transcode [OPTIONS] --to-count-width W --to-len-width W [-o OUT] [INPUT]
+
+OPTIONS: --count-width W, --len-width W (W: u8, u16, u24, u32, u64 or 1, 2, 3, 4, 8)";
+
+///Handler of the profile selected at runtime
+trait Profiled {
+	type Output;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output;
+}
+
+fn profiled<P: Profiled>(count_width: LamanshWidth, len_width: LamanshWidth, p: P) -> P::Output {
+	match count_width {
+		LamanshWidth::U8 => profiled_value::<U8, P>(len_width, p),
+		LamanshWidth::U16 => profiled_value::<U16, P>(len_width, p),
+		LamanshWidth::U24 => profiled_value::<U24, P>(len_width, p),
+		LamanshWidth::U32 => profiled_value::<U32, P>(len_width, p),
+		LamanshWidth::U64 => profiled_value::<U64, P>(len_width, p),
+	}
+}
+
+fn profiled_value<NC: LamanshSized + 'static, P: Profiled>(len_width: LamanshWidth, p: P) -> P::Output {
+	match len_width {
+		LamanshWidth::U8 => p.run::<NC, U8>(),
+		LamanshWidth::U16 => p.run::<NC, U16>(),
+		LamanshWidth::U24 => p.run::<NC, U24>(),
+		LamanshWidth::U32 => p.run::<NC, U32>(),
+		LamanshWidth::U64 => p.run::<NC, U64>(),
+	}
+}
+
+
+struct Encode<'v>(&'v [Vec<u8>]);
+
+impl<'v> Profiled for Encode<'v> {
+	type Output = Result<Vec<u8>, String>;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output {
+		self.0.to_lamansh::<NC, N>().map_err(|e| format!("encode: {:?}", e))
+	}
+}
+
+struct Decode<'v>(&'v [u8]);
+
+impl<'v> Profiled for Decode<'v> {
+	type Output = Result<Vec<&'v [u8]>, String>;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output {
+		let lamansh = new_custom_lamansh::<NC, N>(self.0).map_err(|e| format!("decode: {:?}", e))?;
+		lamansh.into_iter().map(|a| a.map_err(|e| format!("decode: {:?}", e))).collect()
+	}
+}
+
+struct Validate<'v>(&'v [u8]);
+
+impl<'v> Profiled for Validate<'v> {
+	type Output = Result<(), String>;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output {
+		let len = message_len::<NC, N>(self.0).map_err(|e| format!("invalid: {:?}", e))?;
+		if len != self.0.len() {
+			return Err( format!("invalid: {} bytes after the values", self.0.len() - len) );
+		}
+		Ok( () )
+	}
+}
+
+struct Inspect<'v>(&'v [u8]);
+
+impl<'v> Profiled for Inspect<'v> {
+	type Output = Result<String, String>;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output {
+		let values = Decode(self.0).run::<NC, N>()?;
+		let header_len = NC::byted() + N::byted() * values.len();
+
+		let mut result = String::new();
+		result.push_str(&format!("profile: count u{}, len u{}\n", NC::byted() * 8, N::byted() * 8));
+		result.push_str(&format!("elements: {}\n", values.len()));
+		result.push_str(&format!("headers: {} bytes\n", header_len));
+		result.push_str(&format!("values: {} bytes\n", values.iter().map(|a| a.len()).sum::<usize>()));
+		result.push_str(&format!("total: {} bytes\n", self.0.len()));
+
+		let mut offset = header_len;
+		for (i, a) in values.iter().enumerate() {
+			result.push_str(&format!("[{}] offset {} len {}\n", i, offset, a.len()));
+			offset += a.len();
+		}
+		Ok( result )
+	}
+}
+
+struct Transcode<'v> {
+	array: &'v [u8],
+	to_count_width: LamanshWidth,
+	to_len_width: LamanshWidth,
+}
+
+impl<'v> Profiled for Transcode<'v> {
+	type Output = Result<Vec<u8>, String>;
+
+	fn run<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self) -> Self::Output {
+		let lamansh = new_custom_lamansh::<NC, N>(self.array).map_err(|e| format!("decode: {:?}", e))?;
+		profiled(self.to_count_width, self.to_len_width, TranscodeTo(lamansh))
+	}
+}
+
+///Second dispatch of `Transcode`, the profile of the result
+struct TranscodeTo<'v, NC: LamanshSized + 'static, N: LamanshSized + 'static>(Lamansh<'v, NC, N>);
+
+impl<'v, NC: LamanshSized + 'static, N: LamanshSized + 'static> Profiled for TranscodeTo<'v, NC, N> {
+	type Output = Result<Vec<u8>, String>;
+
+	fn run<NC2: LamanshSized + 'static, N2: LamanshSized + 'static>(self) -> Self::Output {
+		transcode::<NC, N, NC2, N2>(&self.0).map_err(|e| format!("transcode: {:?}", e))
+	}
+}
+
+
+struct Args {
+	count_width: LamanshWidth,
+	len_width: LamanshWidth,
+	to_count_width: Option<LamanshWidth>,
+	to_len_width: Option<LamanshWidth>,
+
+	output: Option<String>,
+	out_dir: Option<String>,
+	hex: bool,
+	files: bool,
+
+	free: Vec<String>,
+}
+
+fn parse_width(arg: Option<String>) -> Result<LamanshWidth, String> {
+	let arg = arg.ok_or_else(|| "missing width".to_string())?;
+	let byted = match arg.to_lowercase().as_str() {
+		"u8" | "1" => 1,
+		"u16" | "2" => 2,
+		"u24" | "3" => 3,
+		"u32" | "4" => 4,
+		"u64" | "8" => 8,
+		_ => 0,
+	};
+	LamanshWidth::from_byted(byted).ok_or_else(|| format!("unknown width: {}", arg))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut iter: I) -> Result<Args, String> {
+	let mut args = Args {
+		count_width: LamanshWidth::U8,
+		len_width: LamanshWidth::U64,
+		to_count_width: None,
+		to_len_width: None,
+
+		output: None,
+		out_dir: None,
+		hex: false,
+		files: false,
+
+		free: Vec::new(),
+	};
+
+	while let Some(a) = iter.next() {
+		match a.as_str() {
+			"--count-width" => args.count_width = parse_width(iter.next())?,
+			"--len-width" => args.len_width = parse_width(iter.next())?,
+			"--to-count-width" => args.to_count_width = Some( parse_width(iter.next())? ),
+			"--to-len-width" => args.to_len_width = Some( parse_width(iter.next())? ),
+			"-o" | "--output" => args.output = Some( iter.next().ok_or_else(|| "missing output".to_string())? ),
+			"--out-dir" => args.out_dir = Some( iter.next().ok_or_else(|| "missing directory".to_string())? ),
+			"--hex" => args.hex = true,
+			"--files" => args.files = true,
+			"--" => {
+				args.free.extend(iter);
+				break;
+			},
+			_ if a.starts_with("--") => return Err( format!("unknown option: {}", a) ),
+			_ => args.free.push(a),
+		}
+	}
+
+	Ok( args )
+}
+
+fn read_input(args: &Args) -> io::Result<Vec<u8>> {
+	let mut array = Vec::new();
+	match args.free.first() {
+		Some(a) => { File::open(a)?.read_to_end(&mut array)?; },
+		None => { io::stdin().read_to_end(&mut array)?; },
+	}
+	Ok( array )
+}
+
+fn write_output(args: &Args, array: &[u8]) -> io::Result<()> {
+	match args.output {
+		Some(ref a) => File::create(a)?.write_all(array),
+		None => {
+			let stdout = io::stdout();
+			let mut stdout = stdout.lock();
+			stdout.write_all(array)?;
+			stdout.flush()
+		},
+	}
+}
+
+fn hex(array: &[u8]) -> String {
+	array.iter().map(|a| format!("{:02x}", a)).collect()
+}
+
+fn run(command: &str, args: &Args) -> Result<(), String> {
+	let io_err = |e: io::Error| e.to_string();
+
+	match command {
+		"encode" => {
+			let values: Vec<Vec<u8>> = if args.files {
+				args.free.iter().map(fs::read).collect::<io::Result<_>>().map_err(io_err)?
+			}else
+			if !args.free.is_empty() {
+				args.free.iter().map(|a| a.as_bytes().to_vec()).collect()
+			}else {
+				let stdin = io::stdin();
+				let lines = stdin.lock().lines().collect::<io::Result<Vec<_>>>().map_err(io_err)?;
+				lines.into_iter().map(|a| a.into_bytes()).collect()
+			};
+
+			let array = profiled(args.count_width, args.len_width, Encode(&values))?;
+			write_output(args, &array).map_err(io_err)
+		},
+		"decode" => {
+			let array = read_input(args).map_err(io_err)?;
+			let values = profiled(args.count_width, args.len_width, Decode(&array))?;
+
+			if let Some(ref dir) = args.out_dir {
+				fs::create_dir_all(dir).map_err(io_err)?;
+				for (i, a) in values.iter().enumerate() {
+					fs::write(Path::new(dir).join(i.to_string()), a).map_err(io_err)?;
+				}
+				return Ok( () );
+			}
+
+			let mut result = Vec::new();
+			for a in values.iter() {
+				match args.hex {
+					true => result.extend_from_slice(hex(a).as_bytes()),
+					false => result.extend_from_slice(String::from_utf8_lossy(a).as_bytes()),
+				}
+				result.push(b'\n');
+			}
+			write_output(args, &result).map_err(io_err)
+		},
+		"inspect" => {
+			let array = read_input(args).map_err(io_err)?;
+			let result = profiled(args.count_width, args.len_width, Inspect(&array))?;
+			write_output(args, result.as_bytes()).map_err(io_err)
+		},
+		"validate" => {
+			let array = read_input(args).map_err(io_err)?;
+			profiled(args.count_width, args.len_width, Validate(&array))?;
+			profiled(args.count_width, args.len_width, Decode(&array))?;
+			println!("ok");
+			Ok( () )
+		},
+		"transcode" => {
+			let (to_count_width, to_len_width) = match (args.to_count_width, args.to_len_width) {
+				(Some(a), Some(b)) => (a, b),
+				_ => return Err( "transcode requires --to-count-width and --to-len-width".to_string() ),
+			};
+
+			let array = read_input(args).map_err(io_err)?;
+			let array = profiled(args.count_width, args.len_width, Transcode {
+				array: &array,
+				to_count_width: to_count_width,
+				to_len_width: to_len_width,
+			})?;
+			write_output(args, &array).map_err(io_err)
+		},
+		_ => Err( USAGE.to_string() ),
+	}
+}
+
+fn main() {
+	let mut iter = env::args().skip(1);
+	let command = match iter.next() {
+		Some(a) => a,
+		_ => {
+			eprintln!("{}", USAGE);
+			process::exit(2);
+		},
+	};
+
+	let result = parse_args(iter).and_then(|args| run(&command, &args));
+	if let Err(e) = result {
+		eprintln!("{}", e);
+		process::exit(1);
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::parse_args;
+	use super::parse_width;
+	use super::run;
+	use super::profiled;
+	use super::Decode;
+	use super::Encode;
+	use super::Inspect;
+	use super::Transcode;
+	use cluLamansh::lamansh::sized::LamanshWidth;
+
+	fn args(array: &[&str]) -> Result<super::Args, String> {
+		parse_args(array.iter().map(|a| a.to_string()))
+	}
+
+	#[test]
+	fn widths() {
+		assert_eq!(parse_width(Some("u8".to_string())), Ok( LamanshWidth::U8 ));
+		assert_eq!(parse_width(Some("U24".to_string())), Ok( LamanshWidth::U24 ));
+		assert_eq!(parse_width(Some("8".to_string())), Ok( LamanshWidth::U64 ));
+		assert_eq!(parse_width(Some("u128".to_string())), Err( "unknown width: u128".to_string() ));
+		assert_eq!(parse_width(Some("5".to_string())), Err( "unknown width: 5".to_string() ));
+		assert_eq!(parse_width(None), Err( "missing width".to_string() ));
+	}
+
+	#[test]
+	fn arguments() {
+		let a = args(&["--count-width", "u16", "--len-width", "4", "-o", "out", "--hex", "in", "--", "--files"]).unwrap();
+		assert_eq!((a.count_width, a.len_width), (LamanshWidth::U16, LamanshWidth::U32));
+		assert_eq!(a.output, Some( "out".to_string() ));
+		assert!(a.hex && !a.files);
+		assert_eq!(a.free, vec!["in".to_string(), "--files".to_string()]);
+
+		let a = args(&[]).unwrap();
+		assert_eq!((a.count_width, a.len_width, a.to_count_width), (LamanshWidth::U8, LamanshWidth::U64, None));
+
+		assert_eq!(args(&["--count-width"]).err(), Some( "missing width".to_string() ));
+		assert_eq!(args(&["--to-len-width", "u7"]).err(), Some( "unknown width: u7".to_string() ));
+		assert_eq!(args(&["-o"]).err(), Some( "missing output".to_string() ));
+		assert_eq!(args(&["--out-dir"]).err(), Some( "missing directory".to_string() ));
+		assert_eq!(args(&["--verbose"]).err(), Some( "unknown option: --verbose".to_string() ));
+
+		assert_eq!(run("compress", &args(&[]).unwrap()), Err( super::USAGE.to_string() ));
+		assert!(run("transcode", &args(&["--to-count-width", "u8"]).unwrap()).unwrap_err().contains("--to-len-width"));
+	}
+
+	#[test]
+	fn profiles() {
+		let values = vec![b"first".to_vec(), Vec::new(), vec![0xFF]];
+		let array = profiled(LamanshWidth::U8, LamanshWidth::U16, Encode(&values)).unwrap();
+		assert_eq!(&array[.. 3], &[2, 0, 5]);
+		assert_eq!(profiled(LamanshWidth::U8, LamanshWidth::U16, Decode(&array)).unwrap(), vec![&b"first"[..], &b""[..], &[0xFF][..]]);
+
+		let inspect = profiled(LamanshWidth::U8, LamanshWidth::U16, Inspect(&array)).unwrap();
+		assert!(inspect.contains("elements: 3\n"));
+		assert!(inspect.contains("[2] offset 12 len 1\n"));
+
+		let wide = profiled(LamanshWidth::U8, LamanshWidth::U16, Transcode {
+			array: &array,
+			to_count_width: LamanshWidth::U16,
+			to_len_width: LamanshWidth::U32,
+		}).unwrap();
+		assert_eq!(wide, profiled(LamanshWidth::U16, LamanshWidth::U32, Encode(&values)).unwrap());
+
+		assert!(profiled(LamanshWidth::U8, LamanshWidth::U16, Transcode {
+			array: &wide[.. 3],
+			to_count_width: LamanshWidth::U8,
+			to_len_width: LamanshWidth::U8,
+		}).is_err());
+	}
+}
@@ -51,11 +51,11 @@ impl<'a, T> MutDynExactChunks<'a, T> {
         MutDynExactChunks(slice)
     }
     #[inline]
-    pub fn next_usize(&mut self, usize: usize) -> Option<&mut [T]> {
+    pub fn next_usize(&mut self, usize: usize) -> Option<&'a mut [T]> {
         if self.0.len() < usize {
             None
         } else {
-            let tmp = ::std::mem::replace(&mut self.0, unsafe{ ::std::mem::uninitialized() });
+            let tmp = ::std::mem::replace(&mut self.0, &mut []);
             let (fst, snd) = tmp.split_at_mut(usize);
             self.0 = snd;
             Some(fst)
@@ -0,0 +1,232 @@
+/*!
+C ABI of the protocol with the widths selected at runtime, see `include/cluLamansh.h`.
+
+Widths are given in bytes (1, 2, 3, 4 or 8). Functions return `LAMANSH_OK` or an error code.
+ */
+
+use std::slice;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::envelope::LamanshVisitor;
+use lamansh::envelope::visit_width;
+use lamansh::sized::LamanshSized;
+use lamansh::sized::LamanshWidth;
+use lamansh::sized::U8;
+use lamansh::sized::U16;
+use lamansh::sized::U24;
+use lamansh::sized::U32;
+use lamansh::sized::U64;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+pub const LAMANSH_OK: i32 = 0;
+
+pub const LAMANSH_ERR_EMPTY_LAMANSH: i32 = 1;
+pub const LAMANSH_ERR_GET_SIZE_ARRAY: i32 = 2;
+pub const LAMANSH_ERR_GET_VALUE_HEAD: i32 = 3;
+pub const LAMANSH_ERR_GET_VALUE: i32 = 4;
+pub const LAMANSH_ERR_GET_NEXT_VALUE: i32 = 5;
+pub const LAMANSH_ERR_RECORD_LEN: i32 = 6;
+pub const LAMANSH_ERR_FIELD_VALUE: i32 = 7;
+pub const LAMANSH_ERR_GET_ENVELOPE: i32 = 8;
+pub const LAMANSH_ERR_ENVELOPE_MAGIC: i32 = 9;
+pub const LAMANSH_ERR_ENVELOPE_VERSION: i32 = 10;
+pub const LAMANSH_ERR_ENVELOPE_PROFILE: i32 = 11;
+pub const LAMANSH_ERR_ENVELOPE_FLAGS: i32 = 12;
+pub const LAMANSH_ERR_GET_CHECKSUM: i32 = 13;
+pub const LAMANSH_ERR_GET_INDEX: i32 = 14;
+pub const LAMANSH_ERR_OVERWRITE_LEN: i32 = 15;
+pub const LAMANSH_ERR_CHECKSUM_MISMATCH: i32 = 16;
+pub const LAMANSH_ERR_CODEC: i32 = 17;
+pub const LAMANSH_ERR_GET_TAG: i32 = 18;
+pub const LAMANSH_ERR_AUTH_TAG: i32 = 19;
+pub const LAMANSH_ERR_DEDUP_REF: i32 = 20;
+pub const LAMANSH_ERR_DELTA_BASE: i32 = 21;
+pub const LAMANSH_ERR_DELTA_PATCH: i32 = 22;
+
+pub const LAMANSH_ERR_EMPTY_ARRAY: i32 = 101;
+pub const LAMANSH_ERR_COUNT_OVERFLOW: i32 = 102;
+pub const LAMANSH_ERR_VALUE_OVERFLOW: i32 = 103;
+pub const LAMANSH_ERR_INDEX_OVERFLOW: i32 = 104;
+pub const LAMANSH_ERR_INVALID_LAMANSH: i32 = 105;
+
+///Null pointer in the arguments
+pub const LAMANSH_ERR_NULL: i32 = 201;
+///Unsupported width
+pub const LAMANSH_ERR_WIDTH: i32 = 202;
+
+fn err_code(e: &cluLamanshErr) -> i32 {
+	match *e {
+		cluLamanshErr::ErrEmptyLamansh => LAMANSH_ERR_EMPTY_LAMANSH,
+		cluLamanshErr::ErrGetSizeArray => LAMANSH_ERR_GET_SIZE_ARRAY,
+		cluLamanshErr::ErrGetValueHead => LAMANSH_ERR_GET_VALUE_HEAD,
+		cluLamanshErr::ErrGetValue => LAMANSH_ERR_GET_VALUE,
+		cluLamanshErr::ErrGetNextValue => LAMANSH_ERR_GET_NEXT_VALUE,
+		cluLamanshErr::ErrRecordLen => LAMANSH_ERR_RECORD_LEN,
+		cluLamanshErr::ErrFieldValue => LAMANSH_ERR_FIELD_VALUE,
+		cluLamanshErr::ErrGetEnvelope => LAMANSH_ERR_GET_ENVELOPE,
+		cluLamanshErr::ErrEnvelopeMagic => LAMANSH_ERR_ENVELOPE_MAGIC,
+		cluLamanshErr::ErrEnvelopeVersion => LAMANSH_ERR_ENVELOPE_VERSION,
+		cluLamanshErr::ErrEnvelopeProfile => LAMANSH_ERR_ENVELOPE_PROFILE,
+		cluLamanshErr::ErrEnvelopeFlags => LAMANSH_ERR_ENVELOPE_FLAGS,
+		cluLamanshErr::ErrGetChecksum => LAMANSH_ERR_GET_CHECKSUM,
+		cluLamanshErr::ErrGetIndex => LAMANSH_ERR_GET_INDEX,
+		cluLamanshErr::ErrOverwriteLen => LAMANSH_ERR_OVERWRITE_LEN,
+		cluLamanshErr::ChecksumMismatch { .. } => LAMANSH_ERR_CHECKSUM_MISMATCH,
+		cluLamanshErr::ErrCodec => LAMANSH_ERR_CODEC,
+		cluLamanshErr::ErrGetTag => LAMANSH_ERR_GET_TAG,
+		cluLamanshErr::ErrAuthTag => LAMANSH_ERR_AUTH_TAG,
+		cluLamanshErr::ErrDedupRef => LAMANSH_ERR_DEDUP_REF,
+		cluLamanshErr::ErrDeltaBase => LAMANSH_ERR_DELTA_BASE,
+		cluLamanshErr::ErrDeltaPatch => LAMANSH_ERR_DELTA_PATCH,
+	}
+}
+
+fn build_err_code(e: &ToLamanshErr) -> i32 {
+	match *e {
+		ToLamanshErr::EmptyArray => LAMANSH_ERR_EMPTY_ARRAY,
+		ToLamanshErr::CountOverflow => LAMANSH_ERR_COUNT_OVERFLOW,
+		ToLamanshErr::ValueOverflow => LAMANSH_ERR_VALUE_OVERFLOW,
+		ToLamanshErr::IndexOverflow => LAMANSH_ERR_INDEX_OVERFLOW,
+		ToLamanshErr::InvalidLamansh => LAMANSH_ERR_INVALID_LAMANSH,
+	}
+}
+
+///Parsed protocol, the data is not copied and must live longer than the handle.
+#[derive(Debug)]
+pub struct LamanshHandle {
+	array: *const u8,
+	//offset and len of every value in `array`
+	values: Vec<(usize, usize)>,
+}
+
+//start of the parsed data
+struct HandleVisitor(usize);
+
+impl<'a> LamanshVisitor<'a> for HandleVisitor {
+	type Output = Result<Vec<(usize, usize)>, cluLamanshErr>;
+
+	fn visit<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self, lamansh: cluLamansh<'a, NC, N>) -> Self::Output {
+		let start = self.0;
+		lamansh.into_iter().map(|a| a.map(|a| (a.as_ptr() as usize - start, a.len()))).collect()
+	}
+}
+
+///Parse `len` bytes of `data`, the handle is freed with `lamansh_free`.
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_parse(data: *const u8, len: usize, count_width: u8, len_width: u8, out: *mut *mut LamanshHandle) -> i32 {
+	if data.is_null() || out.is_null() {
+		return LAMANSH_ERR_NULL;
+	}
+	let (count_width, len_width) = match (LamanshWidth::from_byted(count_width as usize), LamanshWidth::from_byted(len_width as usize)) {
+		(Some(a), Some(b)) => (a, b),
+		_ => return LAMANSH_ERR_WIDTH,
+	};
+
+	let array = slice::from_raw_parts(data, len);
+	let values = match visit_width(array, count_width, len_width, HandleVisitor(data as usize)) {
+		Ok(Ok(a)) => a,
+		Ok(Err(e)) | Err(e) => return err_code(&e),
+	};
+
+	*out = Box::into_raw(Box::new(
+		LamanshHandle {
+			array: data,
+			values: values,
+		}
+	));
+	LAMANSH_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_free(handle: *mut LamanshHandle) {
+	if !handle.is_null() {
+		drop(Box::from_raw(handle));
+	}
+}
+
+///Number of elements, 0 for the null handle
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_count(handle: *const LamanshHandle) -> usize {
+	match handle.as_ref() {
+		Some(a) => a.values.len(),
+		None => 0,
+	}
+}
+
+///Pointer to the value of the element in the parsed data and its len.
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_get(handle: *const LamanshHandle, index: usize, out_data: *mut *const u8, out_len: *mut usize) -> i32 {
+	let handle = match handle.as_ref() {
+		Some(a) if !out_data.is_null() && !out_len.is_null() => a,
+		_ => return LAMANSH_ERR_NULL,
+	};
+
+	match handle.values.get(index) {
+		Some(&(n, len)) => {
+			*out_data = handle.array.add(n);
+			*out_len = len;
+			LAMANSH_OK
+		},
+		None => LAMANSH_ERR_GET_INDEX,
+	}
+}
+
+///Build the protocol from `count` values, the buffer is freed with `lamansh_buffer_free`.
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_build(count_width: u8, len_width: u8, data: *const *const u8, lens: *const usize, count: usize, out_data: *mut *mut u8, out_len: *mut usize) -> i32 {
+	if out_data.is_null() || out_len.is_null() || (count != 0 && (data.is_null() || lens.is_null())) {
+		return LAMANSH_ERR_NULL;
+	}
+	let (count_width, len_width) = match (LamanshWidth::from_byted(count_width as usize), LamanshWidth::from_byted(len_width as usize)) {
+		(Some(a), Some(b)) => (a, b),
+		_ => return LAMANSH_ERR_WIDTH,
+	};
+
+	let mut values = Vec::with_capacity(count);
+	for i in 0 .. count {
+		let (a, len) = (*data.add(i), *lens.add(i));
+		values.push(match a.is_null() {
+			true if len == 0 => &[][..],
+			true => return LAMANSH_ERR_NULL,
+			false => slice::from_raw_parts(a, len),
+		});
+	}
+
+	let array = match build_width(&values, count_width, len_width) {
+		Ok(a) => a.into_boxed_slice(),
+		Err(e) => return build_err_code(&e),
+	};
+
+	*out_len = array.len();
+	*out_data = Box::into_raw(array) as *mut u8;
+	LAMANSH_OK
+}
+
+///Free the buffer of `lamansh_build`
+#[no_mangle]
+pub unsafe extern "C" fn lamansh_buffer_free(data: *mut u8, len: usize) {
+	if !data.is_null() {
+		drop(Box::from_raw(slice::from_raw_parts_mut(data, len) as *mut [u8]));
+	}
+}
+
+fn build_width(array: &[&[u8]], count_width: LamanshWidth, len_width: LamanshWidth) -> Result<Vec<u8>, ToLamanshErr> {
+	match count_width {
+		LamanshWidth::U8 => build_value::<U8>(array, len_width),
+		LamanshWidth::U16 => build_value::<U16>(array, len_width),
+		LamanshWidth::U24 => build_value::<U24>(array, len_width),
+		LamanshWidth::U32 => build_value::<U32>(array, len_width),
+		LamanshWidth::U64 => build_value::<U64>(array, len_width),
+	}
+}
+
+fn build_value<NC: LamanshSized + 'static>(array: &[&[u8]], len_width: LamanshWidth) -> Result<Vec<u8>, ToLamanshErr> {
+	match len_width {
+		LamanshWidth::U8 => array.to_lamansh::<NC, U8>(),
+		LamanshWidth::U16 => array.to_lamansh::<NC, U16>(),
+		LamanshWidth::U24 => array.to_lamansh::<NC, U24>(),
+		LamanshWidth::U32 => array.to_lamansh::<NC, U32>(),
+		LamanshWidth::U64 => array.to_lamansh::<NC, U64>(),
+	}
+}
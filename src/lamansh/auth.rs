@@ -0,0 +1,275 @@
+/*!
+Authentication tag of the protocol: the tag of the headers and the values is appended after the values.
+
+The handler is created only after the check of the tag (`cluLamansh::new_auth`).
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::lamansh::auth::LamanshHmacSha256;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::cluLamansh as Lamansh;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let auth = LamanshHmacSha256::new(b"secret key");
+	let mut array = [&b"TEST"[..]].to_lamansh_auth::<U8, U16, _>(&auth).unwrap();
+
+	assert!(Lamansh::<U8, U16>::new_auth(&array, &auth).is_ok());
+
+	array[3] ^= 1;
+	assert!(Lamansh::<U8, U16>::new_auth(&array, &auth).is_err());
+}
+```
+ */
+
+extern crate byteorder;
+use self::byteorder::BigEndian;
+use self::byteorder::ByteOrder;
+
+use lamansh::buffer::LamanshBuffer;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+pub trait LamanshAuthenticator {
+	///Len of the tag
+	fn tag_len(&self) -> usize;
+
+	///Tag of the headers and the values
+	fn sign(&self, array: &[u8]) -> Vec<u8>;
+
+	///Check of the tag, the comparison does not depend on the position of the first difference.
+	fn verify(&self, array: &[u8], tag: &[u8]) -> bool {
+		constant_time_eq(&self.sign(array), tag)
+	}
+}
+
+///Comparison of the slices in time depending only on their len
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	if a.len() != b.len() {
+		return false;
+	}
+
+	let mut diff = 0u8;
+	for (a, b) in a.iter().zip(b.iter()) {
+		diff |= a ^ b;
+	}
+	diff == 0
+}
+
+///Append the tag to the protocol
+pub fn append_tag<A: LamanshAuthenticator>(auth: &A, vec_result: &mut LamanshBuffer) {
+	let len = vec_result.len();
+	let tag = auth.sign(vec_result);
+
+	vec_result.set_len(len + tag.len());
+	vec_result[len ..].copy_from_slice(&tag);
+}
+
+///Check the tag, the protocol without the tag is returned
+pub fn split_tag<'a, A: LamanshAuthenticator>(auth: &A, array: &'a [u8]) -> Result<&'a [u8], cluLamanshErr> {
+	let len = match array.len().checked_sub(auth.tag_len()) {
+		Some(a) => a,
+		_ => return Err( cluLamanshErr::ErrGetTag ),
+	};
+	let (array, tag) = array.split_at(len);
+
+	match auth.verify(array, tag) {
+		true => Ok( array ),
+		false => Err( cluLamanshErr::ErrAuthTag ),
+	}
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, NC, N> {
+	///Create a handler after checking the tag (`ToLamansh::to_lamansh_auth`).
+	#[inline]
+	pub fn new_auth<A: LamanshAuthenticator>(array: &'a [u8], auth: &A) -> Result< Self, cluLamanshErr > {
+		Self::array( split_tag(auth, array)? )
+	}
+}
+
+
+///HMAC-SHA256 tag (32 bytes)
+#[derive(Clone)]
+pub struct LamanshHmacSha256 {
+	//key xor ipad and key xor opad
+	inner: [u8; SHA256_BLOCK_LEN],
+	outer: [u8; SHA256_BLOCK_LEN],
+}
+
+impl LamanshHmacSha256 {
+	pub fn new(key: &[u8]) -> Self {
+		let mut block = [0u8; SHA256_BLOCK_LEN];
+		match key.len() > SHA256_BLOCK_LEN {
+			true => block[.. SHA256_LEN].copy_from_slice(&sha256(key)),
+			false => block[.. key.len()].copy_from_slice(key),
+		}
+
+		let mut inner = [0x36u8; SHA256_BLOCK_LEN];
+		let mut outer = [0x5Cu8; SHA256_BLOCK_LEN];
+		for i in 0 .. SHA256_BLOCK_LEN {
+			inner[i] ^= block[i];
+			outer[i] ^= block[i];
+		}
+
+		Self {
+			inner: inner,
+			outer: outer,
+		}
+	}
+
+	pub fn tag(&self, array: &[u8]) -> [u8; SHA256_LEN] {
+		let mut sha = Sha256::new();
+		sha.update(&self.inner);
+		sha.update(array);
+		let inner = sha.finish();
+
+		let mut sha = Sha256::new();
+		sha.update(&self.outer);
+		sha.update(&inner);
+		sha.finish()
+	}
+}
+
+///The key is not shown
+impl ::std::fmt::Debug for LamanshHmacSha256 {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		write!(f, "LamanshHmacSha256")
+	}
+}
+
+impl LamanshAuthenticator for LamanshHmacSha256 {
+	#[inline]
+	fn tag_len(&self) -> usize {
+		SHA256_LEN
+	}
+
+	#[inline]
+	fn sign(&self, array: &[u8]) -> Vec<u8> {
+		self.tag(array).to_vec()
+	}
+}
+
+
+///Hash len
+pub const SHA256_LEN: usize = 32;
+const SHA256_BLOCK_LEN: usize = 64;
+
+///Incremental SHA-256.
+#[derive(Clone, Debug)]
+pub struct Sha256 {
+	state: [u32; 8],
+	block: [u8; SHA256_BLOCK_LEN],
+	block_len: usize,
+	len: u64,
+}
+
+impl Sha256 {
+	pub fn new() -> Self {
+		Self {
+			state: [
+				0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+			],
+			block: [0; SHA256_BLOCK_LEN],
+			block_len: 0,
+			len: 0,
+		}
+	}
+
+	pub fn update(&mut self, mut array: &[u8]) {
+		self.len = self.len.wrapping_add(array.len() as u64);
+
+		while !array.is_empty() {
+			let len = ::std::cmp::min(SHA256_BLOCK_LEN - self.block_len, array.len());
+			self.block[self.block_len .. self.block_len + len].copy_from_slice(&array[.. len]);
+			self.block_len += len;
+			array = &array[len ..];
+
+			if self.block_len == SHA256_BLOCK_LEN {
+				let block = self.block;
+				self.compress(&block);
+				self.block_len = 0;
+			}
+		}
+	}
+
+	pub fn finish(mut self) -> [u8; SHA256_LEN] {
+		let len = self.len.wrapping_mul(8);
+
+		self.update(&[0x80]);
+		while self.block_len != SHA256_BLOCK_LEN - 8 {
+			self.update(&[0]);
+		}
+		let mut array = [0u8; 8];
+		BigEndian::write_u64(&mut array, len);
+		self.update(&array);
+
+		let mut result = [0u8; SHA256_LEN];
+		for (a, b) in result.chunks_mut(4).zip(self.state.iter()) {
+			BigEndian::write_u32(a, *b);
+		}
+		result
+	}
+
+	fn compress(&mut self, block: &[u8; SHA256_BLOCK_LEN]) {
+		let mut w = [0u32; 64];
+		for (i, a) in block.chunks(4).enumerate() {
+			w[i] = BigEndian::read_u32(a);
+		}
+		for i in 16 .. 64 {
+			let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+			let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+			w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+		}
+
+		let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+		for i in 0 .. 64 {
+			let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+			let ch = (e & f) ^ (!e & g);
+			let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+			let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+			let maj = (a & b) ^ (a & c) ^ (b & c);
+			let t2 = s0.wrapping_add(maj);
+
+			h = g;
+			g = f;
+			f = e;
+			e = d.wrapping_add(t1);
+			d = c;
+			c = b;
+			b = a;
+			a = t1.wrapping_add(t2);
+		}
+
+		for (s, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+			*s = s.wrapping_add(*v);
+		}
+	}
+}
+
+impl Default for Sha256 {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[inline]
+pub fn sha256(array: &[u8]) -> [u8; SHA256_LEN] {
+	let mut sha = Sha256::new();
+	sha.update(array);
+	sha.finish()
+}
+
+static SHA256_K: [u32; 64] = [
+	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+	0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+	0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+	0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+	0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+	0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
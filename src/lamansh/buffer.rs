@@ -5,6 +5,10 @@ Eliminating the re-creation of buffers when creating a binary protocol.
 
 use std::ops::DerefMut;
 use std::ops::Deref;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::header_len;
 
 ///Eliminating the re-creation of buffers when creating a binary protocol.
 #[derive(Debug)]
@@ -25,22 +29,64 @@ impl LamanshBuffer {
           LamanshBuffer(array)
      }
 
+     ///Change the len without initializing new bytes, they must be overwritten.
      #[inline]
      pub fn set_len(&mut self, new_len: usize) {
-          let capacity = self.0.capacity();
-          if capacity > new_len {
-               unsafe {
-                    self.0.set_len(new_len);
-               }
-               
-          }else
+          let len = self.0.len();
+          if len < new_len {
+               //reserve is counted from len, not from capacity
+               self.0.reserve(new_len - len);
+          }
+          unsafe {
+               self.0.set_len(new_len);
+          }
+     }
+}
+
+impl LamanshBuffer {
+     ///Add the elements of the message to the end of the protocol in the buffer, the profile must match.
+     ///The buffer must contain only the protocol, without bytes after the values.
+     pub fn append_message<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(&mut self, lamansh: &cluLamansh<'a, NC, N>) -> Result<(), ToLamanshErr> {
+          let values_len = match lamansh.values_len() {
+               Some(a) => a,
+               _ => return Err( ToLamanshErr::InvalidLamansh ),
+          };
+          if self.0.is_empty() {
+               let len = NC::byted() + lamansh.value_head_array.len() + values_len;
+               self.0.extend_from_slice(&lamansh.array[.. len]);
+               return Ok( () );
+          }
 
-          if capacity < new_len {
-               self.0.reserve(new_len - capacity);
-               unsafe {
-                    self.0.set_len(new_len);
-               }
+          let n = match header_len::<NC, N>(&self.0) {
+               Ok(a) => a,
+               _ => return Err( ToLamanshErr::InvalidLamansh ),
+          };
+          //the values of the buffer must end the buffer, otherwise the trailing bytes would be moved between the values
+          let mut len_n = 0usize;
+          for a in self.0[NC::byted() .. n].chunks(N::byted()) {
+               len_n = match len_n.checked_add(N::read_usize(a)) {
+                    Some(a) => a,
+                    _ => return Err( ToLamanshErr::InvalidLamansh ),
+               };
+          }
+          if n.checked_add(len_n) != Some(self.0.len()) {
+               return Err( ToLamanshErr::InvalidLamansh );
+          }
+          let count = (n - NC::byted()) / N::byted() + lamansh.len();
+          if count > NC::max_value() {
+               return Err( ToLamanshErr::CountOverflow );
           }
+
+          let head_len = lamansh.value_head_array.len();
+          let end = self.0.len();
+
+          self.0.resize(end + head_len + values_len, 0);
+          self.0.copy_within(n .. end, n + head_len);
+          self.0[n .. n + head_len].copy_from_slice(lamansh.value_head_array);
+          self.0[end + head_len .. ].copy_from_slice(&lamansh.value_array[.. values_len]);
+          NC::write_usize(count - 1, &mut self.0[.. NC::byted()]);
+
+          Ok( () )
      }
 }
 
@@ -4,6 +4,9 @@
 
 use lamansh::buffer::LamanshBuffer;
 use lamansh::sized::LamanshSized;
+use lamansh::checksum;
+use lamansh::auth;
+use lamansh::auth::LamanshAuthenticator;
 
 ///Generalized methods of obtaining a protocol
 pub trait ToLamansh {
@@ -24,6 +27,38 @@ pub trait ToLamansh {
 
 	//Unsafe method of creating binary data. Do not check the size of the input data. Using an external buffer.
 	fn unsafe_update_buffer<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, vec_result: &mut LamanshBuffer);
+
+	///Safe method of creating binary data with the checksum trailer. Using an external buffer.
+	fn update_buffer_checksum<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+		self.update_buffer::<NC, N>(vec_result)?;
+		checksum::append_checksum(vec_result);
+
+		Ok( () )
+	}
+
+	///Safe method of creating binary data with the checksum trailer.
+	fn to_lamansh_checksum<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self) -> Result<Vec<u8>, ToLamanshErr> {
+		let mut buffer = LamanshBuffer::new();
+		self.update_buffer_checksum::<NC, N>(&mut buffer)?;
+
+		Ok( buffer.into() )
+	}
+
+	///Safe method of creating binary data with the authentication tag. Using an external buffer.
+	fn update_buffer_auth<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: LamanshAuthenticator>(&self, auth: &A, vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+		self.update_buffer::<NC, N>(vec_result)?;
+		auth::append_tag(auth, vec_result);
+
+		Ok( () )
+	}
+
+	///Safe method of creating binary data with the authentication tag.
+	fn to_lamansh_auth<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: LamanshAuthenticator>(&self, auth: &A) -> Result<Vec<u8>, ToLamanshErr> {
+		let mut buffer = LamanshBuffer::new();
+		self.update_buffer_auth::<NC, N, A>(auth, &mut buffer)?;
+
+		Ok( buffer.into() )
+	}
 }
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -42,7 +77,13 @@ pub enum ToLamanshErr {
 	ErrSmallVSized,*/
 
 	CountOverflow,
-	ValueOverflow
+	ValueOverflow,
+
+	///The element with the index is missing
+	IndexOverflow,
+
+	///The source protocol is damaged, the headers go beyond the values
+	InvalidLamansh,
 }
 
 
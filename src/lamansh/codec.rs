@@ -0,0 +1,321 @@
+/*!
+Codecs of the values: identity and the LZ block compressor.
+
+Per element: every value starts with the codec id (1 byte), `0` - the value is stored as is
+(also when the codec does not reduce the value), the decoding iterator does not copy such values.
+
+Per message: the body of the envelope is encoded, the codec id is stored in the flags of the envelope
+(`LamanshEnvelope::wrap_codec`, `LamanshEnveloped::decode_body`).
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::codec::LamanshLz;
+use cluLamansh::lamansh::codec::to_lamansh_codec;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U32;
+
+pub fn main() {
+	let text = "text ".repeat(100);
+	let array = to_lamansh_codec::<U8, U32, _, _>(&LamanshLz, &[text.as_bytes(), b"id"]).unwrap();
+	assert!(array.len() < 100);
+
+	let lamansh = new_custom_lamansh::<U8, U32>(&array).unwrap();
+	let mut iter = lamansh.iter_codec(&LamanshLz);
+	assert_eq!(&*iter.next().unwrap().unwrap(), text.as_bytes());
+	assert_eq!(&*iter.next().unwrap().unwrap(), &b"id"[..]);
+	assert!(iter.next().is_none());
+}
+```
+ */
+
+use std::borrow::Cow;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::iter::cluLamanshIntoIter;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Id of the value stored as is
+pub const LAMANSH_CODEC_IDENTITY: u8 = 0;
+///Id of `LamanshLz`
+pub const LAMANSH_CODEC_LZ: u8 = 1;
+
+///Largest codec id, the id takes 3 bits of the envelope flags
+pub const LAMANSH_CODEC_MAX: u8 = 7;
+
+pub trait LamanshCodec {
+	///Id of the codec, `1 ..= LAMANSH_CODEC_MAX` (`0` - identity)
+	fn id(&self) -> u8;
+
+	fn encode(&self, array: &[u8]) -> Vec<u8>;
+	fn decode(&self, array: &[u8]) -> Result<Vec<u8>, cluLamanshErr>;
+}
+
+///Values are stored as is
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LamanshIdentity;
+
+impl LamanshCodec for LamanshIdentity {
+	#[inline]
+	fn id(&self) -> u8 {
+		LAMANSH_CODEC_IDENTITY
+	}
+
+	#[inline]
+	fn encode(&self, array: &[u8]) -> Vec<u8> {
+		array.to_vec()
+	}
+
+	#[inline]
+	fn decode(&self, array: &[u8]) -> Result<Vec<u8>, cluLamanshErr> {
+		Ok( array.to_vec() )
+	}
+}
+
+
+///Build the protocol, every value is encoded with the codec.
+pub fn to_lamansh_codec<NC: LamanshSized + 'static, N: LamanshSized + 'static, C: LamanshCodec, A: AsRef<[u8]>>(codec: &C, array: &[A]) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut values = Vec::with_capacity(array.len());
+	for a in array.iter() {
+		let a = a.as_ref();
+
+		let encoded = codec.encode(a);
+		let mut value = Vec::with_capacity(1 + ::std::cmp::min(a.len(), encoded.len()));
+		match encoded.len() < a.len() {
+			true => {
+				value.push(codec.id());
+				value.extend_from_slice(&encoded);
+			},
+			false => {
+				value.push(LAMANSH_CODEC_IDENTITY);
+				value.extend_from_slice(a);
+			},
+		}
+		values.push(value);
+	}
+
+	values.to_lamansh::<NC, N>()
+}
+
+///Value without the codec id, the values of the identity codec are not copied.
+pub fn decode_value<'a, C: LamanshCodec>(codec: &C, array: &'a [u8]) -> Result<Cow<'a, [u8]>, cluLamanshErr> {
+	match array.split_first() {
+		Some((&LAMANSH_CODEC_IDENTITY, a)) => Ok( Cow::Borrowed(a) ),
+		Some((id, a)) if *id == codec.id() => Ok( Cow::Owned(codec.decode(a)?) ),
+		_ => Err( cluLamanshErr::ErrCodec ),
+	}
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, NC, N> {
+	///Decoding iterator of the protocol built with `to_lamansh_codec`
+	#[inline]
+	pub fn iter_codec<'c, C: LamanshCodec>(&self, codec: &'c C) -> cluLamanshCodecIter<'a, 'c, NC, N, C> {
+		cluLamanshCodecIter {
+			iter: self.clone().into_iter(),
+			codec: codec,
+		}
+	}
+}
+
+#[derive(Debug)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshCodecIter<'a, 'c, NC: LamanshSized + 'static, N: LamanshSized + 'static, C: LamanshCodec + 'c> {
+	iter: cluLamanshIntoIter<'a, NC, N>,
+	codec: &'c C,
+}
+
+impl<'a, 'c, NC: LamanshSized + 'static, N: LamanshSized + 'static, C: LamanshCodec + 'c> Iterator for cluLamanshCodecIter<'a, 'c, NC, N, C> {
+	type Item = Result<Cow<'a, [u8]>, cluLamanshErr>;
+
+	#[inline]
+	fn next(&mut self) -> Option< Self::Item > {
+		let a = self.iter.next()?;
+		Some( a.and_then(|a| decode_value(self.codec, a)) )
+	}
+}
+
+
+///LZ block compressor: the len of the data (LEB128), then sequences of literals and matches.
+///
+///Sequence: token (literals len << 4 | match len - 4, 15 - the len continues in the next bytes as in LZ4),
+///literals, match offset (2, Big Endian) and the continuation of the match len.
+///The last sequence contains only literals.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LamanshLz;
+
+const LZ_MIN_MATCH: usize = 4;
+const LZ_MAX_OFFSET: usize = 0xFFFF;
+const LZ_HASH_LOG: usize = 12;
+
+#[inline]
+fn lz_hash(array: &[u8]) -> usize {
+	let a = (array[0] as u32) | (array[1] as u32) << 8 | (array[2] as u32) << 16 | (array[3] as u32) << 24;
+	(a.wrapping_mul(2654435761) >> (32 - LZ_HASH_LOG)) as usize
+}
+
+fn lz_write_len(mut len: usize, result: &mut Vec<u8>) {
+	while len >= 255 {
+		result.push(255);
+		len -= 255;
+	}
+	result.push(len as u8);
+}
+
+fn lz_write_sequence(literals: &[u8], offset: usize, match_len: usize, result: &mut Vec<u8>) {
+	let lit_token = ::std::cmp::min(literals.len(), 15);
+	let match_token = match match_len {
+		0 => 0,
+		a => ::std::cmp::min(a - LZ_MIN_MATCH, 15),
+	};
+	result.push((lit_token << 4 | match_token) as u8);
+	if lit_token == 15 {
+		lz_write_len(literals.len() - 15, result);
+	}
+	result.extend_from_slice(literals);
+
+	if match_len != 0 {
+		result.push((offset >> 8) as u8);
+		result.push(offset as u8);
+		if match_token == 15 {
+			lz_write_len(match_len - LZ_MIN_MATCH - 15, result);
+		}
+	}
+}
+
+struct LzReader<'a> {
+	array: &'a [u8],
+	n: usize,
+}
+
+impl<'a> LzReader<'a> {
+	#[inline]
+	fn byte(&mut self) -> Result<u8, cluLamanshErr> {
+		let a = *self.array.get(self.n).ok_or(cluLamanshErr::ErrCodec)?;
+		self.n += 1;
+		Ok( a )
+	}
+
+	fn len(&mut self, token: usize) -> Result<usize, cluLamanshErr> {
+		let mut len = token;
+		if token == 15 {
+			loop {
+				let a = self.byte()?;
+				len = len.checked_add(a as usize).ok_or(cluLamanshErr::ErrCodec)?;
+				if a != 255 {
+					break;
+				}
+			}
+		}
+		Ok( len )
+	}
+}
+
+impl LamanshCodec for LamanshLz {
+	#[inline]
+	fn id(&self) -> u8 {
+		LAMANSH_CODEC_LZ
+	}
+
+	fn encode(&self, array: &[u8]) -> Vec<u8> {
+		let mut result = Vec::with_capacity(array.len() / 2 + 16);
+		let mut len = array.len() as u64;
+		loop {
+			match len < 0x80 {
+				true => {
+					result.push(len as u8);
+					break;
+				},
+				false => result.push((len & 0x7F) as u8 | 0x80),
+			}
+			len >>= 7;
+		}
+
+		let mut table = vec![0usize; 1 << LZ_HASH_LOG];
+		let (mut n, mut anchor) = (0, 0);
+		while n + LZ_MIN_MATCH <= array.len() {
+			let hash = lz_hash(&array[n ..]);
+			//positions are stored + 1, 0 - empty
+			let candidate = table[hash];
+			table[hash] = n + 1;
+
+			if candidate != 0 && n - (candidate - 1) <= LZ_MAX_OFFSET && array[candidate - 1 .. candidate - 1 + LZ_MIN_MATCH] == array[n .. n + LZ_MIN_MATCH] {
+				let m = candidate - 1;
+				let mut match_len = LZ_MIN_MATCH;
+				while n + match_len < array.len() && array[m + match_len] == array[n + match_len] {
+					match_len += 1;
+				}
+
+				lz_write_sequence(&array[anchor .. n], n - m, match_len, &mut result);
+				n += match_len;
+				anchor = n;
+			}else {
+				n += 1;
+			}
+		}
+		lz_write_sequence(&array[anchor ..], 0, 0, &mut result);
+
+		result
+	}
+
+	fn decode(&self, array: &[u8]) -> Result<Vec<u8>, cluLamanshErr> {
+		let mut reader = LzReader {
+			array: array,
+			n: 0,
+		};
+
+		let mut len = 0u64;
+		let mut shift = 0;
+		loop {
+			let a = reader.byte()?;
+			if shift > 63 {
+				return Err( cluLamanshErr::ErrCodec );
+			}
+			len |= ((a & 0x7F) as u64) << shift;
+			if a & 0x80 == 0 {
+				break;
+			}
+			shift += 7;
+		}
+		if len > usize::max_value() as u64 {
+			return Err( cluLamanshErr::ErrCodec );
+		}
+		let len = len as usize;
+
+		//the declared len is not trusted for the allocation
+		let mut result = Vec::with_capacity(::std::cmp::min(len, array.len().saturating_mul(4)));
+		loop {
+			let token = reader.byte()? as usize;
+
+			let lit_len = reader.len(token >> 4)?;
+			let literals = match reader.n.checked_add(lit_len).and_then(|a| array.get(reader.n .. a)) {
+				Some(a) => a,
+				_ => return Err( cluLamanshErr::ErrCodec ),
+			};
+			reader.n += lit_len;
+			if result.len() + lit_len > len {
+				return Err( cluLamanshErr::ErrCodec );
+			}
+			result.extend_from_slice(literals);
+
+			if result.len() == len && reader.n == array.len() {
+				return Ok( result );
+			}
+
+			let offset = (reader.byte()? as usize) << 8 | reader.byte()? as usize;
+			let match_len = reader.len(token & 0x0F)?.checked_add(LZ_MIN_MATCH).ok_or(cluLamanshErr::ErrCodec)?;
+			if offset == 0 || offset > result.len() || match_len > len - result.len() {
+				return Err( cluLamanshErr::ErrCodec );
+			}
+
+			//the match may overlap the copied bytes
+			let start = result.len() - offset;
+			for i in 0 .. match_len {
+				let a = result[start + i];
+				result.push(a);
+			}
+		}
+	}
+}
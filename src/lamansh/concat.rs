@@ -0,0 +1,55 @@
+/*! Merging messages of one profile without decoding the values. */
+
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+
+///Merge messages: the count headers are summed, the value headers and the values are joined.
+pub fn concat<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(array: &[cluLamansh<'a, NC, N>]) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut buffer = LamanshBuffer::new();
+	update_buffer_concat(array, &mut buffer)?;
+
+	Ok( buffer.into() )
+}
+
+///Merge messages. Using an external buffer.
+pub fn update_buffer_concat<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(array: &[cluLamansh<'a, NC, N>], vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+	if array.is_empty() {
+		return Err( ToLamanshErr::EmptyArray );
+	}
+
+	let mut len_elements = 0;
+	let mut len_n = 0;
+	for lamansh in array.iter() {
+		len_elements += lamansh.len();
+		len_n += match lamansh.values_len() {
+			Some(a) => a,
+			_ => return Err( ToLamanshErr::InvalidLamansh ),
+		};
+	}
+	if len_elements > NC::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+	vec_result.set_len(n_c_sized + (n_sized * len_elements) + len_n);
+
+	NC::write_usize(len_elements-1, &mut vec_result[.. n_c_sized]);
+
+	let mut n = n_c_sized;
+	let mut n_vh = n + ( n_sized * len_elements );
+	for lamansh in array.iter() {
+		let head = lamansh.value_head_array;
+		vec_result[n .. n + head.len()].copy_from_slice(head);
+		n += head.len();
+
+		//already checked
+		let values = &lamansh.value_array[.. lamansh.values_len().unwrap_or(0)];
+		vec_result[n_vh .. n_vh + values.len()].copy_from_slice(values);
+		n_vh += values.len();
+	}
+
+	Ok( () )
+}
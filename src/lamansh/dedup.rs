@@ -0,0 +1,214 @@
+/*!
+Deduplicating variant of the protocol (`LAMANSH_FLAG_DEDUP` in the envelope).
+
+The high bit of the header of the element marks the reference, the other bits contain the index
+of the earlier element whose value is repeated. Otherwise the header is the len of the value,
+so the len of one value must fit into `N` without the high bit.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::lamansh::dedup::cluLamanshDedup;
+use cluLamansh::lamansh::dedup::to_lamansh_dedup;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let values = [&b"active"[..], &b"blocked"[..], &b"active"[..], &b"active"[..]];
+	let array = to_lamansh_dedup::<U8, U16, _>(&values).unwrap();
+
+	let lamansh = cluLamanshDedup::<U8, U16>::new(&array).unwrap();
+	assert_eq!(lamansh.iter().collect::<Vec<_>>(), values.to_vec());
+	assert_eq!(lamansh.stats().saved_bytes, 12);
+}
+```
+ */
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::Range;
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamanshErr;
+use lamansh::header_len;
+
+///High bit of the header of `N`
+#[inline]
+fn ref_flag<N: LamanshSized + 'static>() -> usize {
+	1 << (N::bits() - 1)
+}
+
+///Largest len of the value and largest index of the reference
+#[inline]
+pub fn max_dedup_value<N: LamanshSized + 'static>() -> usize {
+	ref_flag::<N>() - 1
+}
+
+///Build the deduplicating protocol from the values.
+pub fn to_lamansh_dedup<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]>>(array: &[A]) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut buffer = LamanshBuffer::new();
+	update_buffer_dedup::<NC, N, A>(array, &mut buffer)?;
+
+	Ok( buffer.into() )
+}
+
+///Build the deduplicating protocol from the values. Using an external buffer.
+///
+///The repeated value refers to its first occurrence, empty values and the values
+///whose first occurrence does not fit into the header are stored as is.
+pub fn update_buffer_dedup<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]>>(array: &[A], vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+	let len_elements = array.len();
+	if len_elements == 0 {
+		return Err( ToLamanshErr::EmptyArray );
+	}
+	if len_elements > NC::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+
+	let max = max_dedup_value::<N>();
+	//header of every element and the index of the first occurrence of the value
+	let mut heads = Vec::with_capacity(len_elements);
+	let mut first: HashMap<&[u8], usize> = HashMap::with_capacity(len_elements);
+	let mut len_n = 0;
+	for (i, a) in array.iter().enumerate() {
+		let a = a.as_ref();
+		if a.len() > max {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+
+		match first.get(a) {
+			Some(&index) if !a.is_empty() => heads.push(ref_flag::<N>() | index),
+			_ => {
+				if i <= max {
+					first.entry(a).or_insert(i);
+				}
+				heads.push(a.len());
+				len_n += a.len();
+			},
+		}
+	}
+
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+	vec_result.set_len(n_c_sized + (n_sized * len_elements) + len_n);
+
+	NC::write_usize(len_elements-1, &mut vec_result[.. n_c_sized]);
+
+	let mut n = n_c_sized;
+	let mut n_vh = n + ( n_sized * len_elements );
+	for (a, head) in array.iter().zip(heads.into_iter()) {
+		N::write_usize(head, &mut vec_result[n .. n + n_sized]);
+		n += n_sized;
+
+		if head & ref_flag::<N>() == 0 {
+			let a = a.as_ref();
+			vec_result[n_vh .. n_vh + a.len()].copy_from_slice(a);
+			n_vh += a.len();
+		}
+	}
+
+	Ok( () )
+}
+
+
+///Size of the deduplicated protocol
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LamanshDedupStats {
+	///Number of the elements stored as references
+	pub references: usize,
+	///Bytes of the values stored in the protocol
+	pub stored_bytes: usize,
+	///Bytes of the values not stored thanks to the references
+	pub saved_bytes: usize,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshDedup<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	value_array: &'a [u8],
+	//resolved position of the value of every element relative to the start of the values
+	ranges: Vec<Range<usize>>,
+	references: usize,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshDedup<'a, NC, N> {
+	///Create a handler, the references are resolved and must point to the earlier elements.
+	pub fn new(array: &'a [u8]) -> Result< Self, cluLamanshErr > {
+		let n = header_len::<NC, N>(array)?;
+		let value_head_array = &array[NC::byted() .. n];
+		let value_array = &array[n ..];
+
+		let mut ranges: Vec<Range<usize>> = Vec::with_capacity(value_head_array.len() / N::byted());
+		let mut references = 0;
+		let mut end = 0usize;
+		for a in value_head_array.chunks(N::byted()) {
+			let head = N::read_usize(a);
+			if head & ref_flag::<N>() != 0 {
+				let range = match ranges.get(head & !ref_flag::<N>()) {
+					Some(a) => a.clone(),
+					None => return Err( cluLamanshErr::ErrDedupRef ),
+				};
+				ranges.push(range);
+				references += 1;
+				continue;
+			}
+
+			let next = match end.checked_add(head) {
+				Some(a) if a <= value_array.len() => a,
+				_ => return Err( cluLamanshErr::ErrGetNextValue ),
+			};
+			ranges.push(end .. next);
+			end = next;
+		}
+
+		Ok(
+			Self {
+				value_array: value_array,
+				ranges: ranges,
+				references: references,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.ranges.len()
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+		let array: &'a [u8] = self.value_array;
+		self.ranges.get(index).map(|a| &array[a.clone()])
+	}
+
+	#[inline]
+	pub fn iter<'b>(&'b self) -> impl Iterator<Item = &'a [u8]> + 'b {
+		let array: &'a [u8] = self.value_array;
+		self.ranges.iter().map(move |a| &array[a.clone()])
+	}
+
+	pub fn stats(&self) -> LamanshDedupStats {
+		let total = self.ranges.iter().map(|a| a.len()).sum::<usize>();
+		let stored_bytes = self.ranges.iter().map(|a| a.end).max().unwrap_or(0);
+
+		LamanshDedupStats {
+			references: self.references,
+			stored_bytes: stored_bytes,
+			saved_bytes: total - stored_bytes,
+		}
+	}
+
+	///Convert into the classic protocol
+	pub fn to_lamansh(&self) -> Result<Vec<u8>, ToLamanshErr> {
+		self.iter().collect::<Vec<_>>().to_lamansh::<NC, N>()
+	}
+}
@@ -0,0 +1,171 @@
+/*!
+Delta of the protocol against the previous message: only the changed elements are sent.
+
+Patch: hash of the base message (the first `LAMANSH_DELTA_HASH_LEN` bytes of SHA-256),
+the new number of elements - 1 (`NC`), the number of the changed elements (`NC`),
+then the index (`NC`), the len (`N`) and the value of every changed element.
+The indexes increase, the elements after the end of the base message are always in the patch.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::delta::apply_delta;
+use cluLamansh::lamansh::delta::encode_delta;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let prev = [&b"20.5"[..], &b"ok"[..], &b"1013"[..]].to_lamansh::<U8, U16>().unwrap();
+	let next = [&b"20.7"[..], &b"ok"[..], &b"1013"[..]];
+
+	let prev = new_custom_lamansh::<U8, U16>(&prev).unwrap();
+	let patch = encode_delta(&prev, &next).unwrap();
+
+	assert_eq!(apply_delta(&prev, &patch).unwrap(), next.to_lamansh::<U8, U16>().unwrap());
+}
+```
+ */
+
+use lamansh::auth::sha256;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Len of the hash of the base message
+pub const LAMANSH_DELTA_HASH_LEN: usize = 8;
+
+///Hash of the base message stored in the patch
+pub fn delta_base_hash<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(prev: &cluLamansh<'a, NC, N>) -> [u8; LAMANSH_DELTA_HASH_LEN] {
+	let mut result = [0u8; LAMANSH_DELTA_HASH_LEN];
+	result.copy_from_slice(&sha256(prev.array)[.. LAMANSH_DELTA_HASH_LEN]);
+	result
+}
+
+///Patch turning `prev` into the protocol of the values `next`.
+pub fn encode_delta<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]>>(prev: &cluLamansh<'a, NC, N>, next: &[A]) -> Result<Vec<u8>, ToLamanshErr> {
+	let len_elements = next.len();
+	if len_elements == 0 {
+		return Err( ToLamanshErr::EmptyArray );
+	}
+	if len_elements > NC::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+
+	let mut prev_array = Vec::with_capacity(prev.len());
+	for a in prev.clone().into_iter() {
+		match a {
+			Ok(a) => prev_array.push(a),
+			Err(_) => return Err( ToLamanshErr::InvalidLamansh ),
+		}
+	}
+
+	let mut changed = Vec::new();
+	for (i, a) in next.iter().enumerate() {
+		let a = a.as_ref();
+		if a.len() > N::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+		if prev_array.get(i) != Some(&a) {
+			changed.push((i, a));
+		}
+	}
+
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+	let len_n = changed.iter().map(|a| n_c_sized + n_sized + a.1.len()).sum::<usize>();
+	let mut result = vec![0u8; LAMANSH_DELTA_HASH_LEN + n_c_sized * 2 + len_n];
+
+	result[.. LAMANSH_DELTA_HASH_LEN].copy_from_slice(&delta_base_hash(prev));
+	let mut n = LAMANSH_DELTA_HASH_LEN;
+	NC::write_usize(len_elements - 1, &mut result[n .. n + n_c_sized]);
+	n += n_c_sized;
+	NC::write_usize(changed.len(), &mut result[n .. n + n_c_sized]);
+	n += n_c_sized;
+
+	for (i, a) in changed.into_iter() {
+		NC::write_usize(i, &mut result[n .. n + n_c_sized]);
+		n += n_c_sized;
+		N::write_usize(a.len(), &mut result[n .. n + n_sized]);
+		n += n_sized;
+
+		result[n .. n + a.len()].copy_from_slice(a);
+		n += a.len();
+	}
+
+	Ok( result )
+}
+
+///Protocol reconstructed from `prev` and the patch of `encode_delta`.
+pub fn apply_delta<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(prev: &cluLamansh<'a, NC, N>, patch: &[u8]) -> Result<Vec<u8>, cluLamanshErr> {
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+
+	if patch.len() < LAMANSH_DELTA_HASH_LEN + n_c_sized * 2 {
+		return Err( cluLamanshErr::ErrDeltaPatch );
+	}
+	if patch[.. LAMANSH_DELTA_HASH_LEN] != delta_base_hash(prev) {
+		return Err( cluLamanshErr::ErrDeltaBase );
+	}
+
+	let mut n = LAMANSH_DELTA_HASH_LEN;
+	let len_elements = match NC::read_usize(&patch[n .. n + n_c_sized]).checked_add(1) {
+		Some(a) if a <= NC::max_value() => a,
+		_ => return Err( cluLamanshErr::ErrDeltaPatch ),
+	};
+	n += n_c_sized;
+	let len_changed = NC::read_usize(&patch[n .. n + n_c_sized]);
+	n += n_c_sized;
+
+	//the counts of the patch are checked before the allocation, every changed element takes an index and a len in the patch
+	if len_changed > (patch.len() - n) / (n_c_sized + n_sized) {
+		return Err( cluLamanshErr::ErrDeltaPatch );
+	}
+	match prev.len().checked_add(len_changed) {
+		Some(a) if len_elements <= a && len_changed <= len_elements => {},
+		_ => return Err( cluLamanshErr::ErrDeltaPatch ),
+	}
+
+	let mut array = Vec::with_capacity(len_elements);
+	for a in prev.clone().into_iter().take(len_elements) {
+		array.push(a?);
+	}
+
+	let mut last = None;
+	for _ in 0 .. len_changed {
+		let i = match patch.get(n .. n + n_c_sized) {
+			Some(a) => NC::read_usize(a),
+			None => return Err( cluLamanshErr::ErrDeltaPatch ),
+		};
+		n += n_c_sized;
+		//the indexes increase and the new elements follow the existing ones
+		if i >= len_elements || last.map_or(false, |a| i <= a) || i > array.len() {
+			return Err( cluLamanshErr::ErrDeltaPatch );
+		}
+		last = Some(i);
+
+		let len = match patch.get(n .. n + n_sized) {
+			Some(a) => N::read_usize(a),
+			None => return Err( cluLamanshErr::ErrDeltaPatch ),
+		};
+		n += n_sized;
+		let value = match n.checked_add(len).and_then(|a| patch.get(n .. a)) {
+			Some(a) => a,
+			None => return Err( cluLamanshErr::ErrDeltaPatch ),
+		};
+		n += len;
+
+		match i == array.len() {
+			true => array.push(value),
+			false => array[i] = value,
+		}
+	}
+	if array.len() != len_elements || n != patch.len() {
+		return Err( cluLamanshErr::ErrDeltaPatch );
+	}
+
+	array.to_lamansh::<NC, N>().map_err(|_| cluLamanshErr::ErrDeltaPatch)
+}
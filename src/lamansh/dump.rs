@@ -0,0 +1,142 @@
+/*!
+Annotated hex dump of the protocol and `Debug` of the handler.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::dump::LamanshDump;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let array = [&b"TEST"[..], &b""[..]].to_lamansh::<U8, U16>().unwrap();
+	let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+
+	println!("{}", LamanshDump::new(&lamansh));
+	println!("{:#?}", lamansh);
+}
+```
+ */
+
+use std::ascii;
+use std::fmt;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+
+///Value bytes shown by default, the rest is truncated
+pub const LAMANSH_DUMP_LIMIT: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct LamanshDump<'a: 'b, 'b, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	lamansh: &'b cluLamansh<'a, NC, N>,
+	limit: usize,
+}
+
+impl<'a: 'b, 'b, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshDump<'a, 'b, NC, N> {
+	#[inline]
+	pub fn new(lamansh: &'b cluLamansh<'a, NC, N>) -> Self {
+		Self {
+			lamansh: lamansh,
+			limit: LAMANSH_DUMP_LIMIT,
+		}
+	}
+
+	///Value bytes shown before truncation
+	#[inline]
+	pub fn with_limit(mut self, limit: usize) -> Self {
+		self.limit = limit;
+		self
+	}
+}
+
+fn write_hex(f: &mut fmt::Formatter, array: &[u8]) -> fmt::Result {
+	for (i, a) in array.iter().enumerate() {
+		if i != 0 {
+			write!(f, " ")?;
+		}
+		write!(f, "{:02x}", a)?;
+	}
+	Ok( () )
+}
+
+impl<'a: 'b, 'b, NC: LamanshSized + 'static, N: LamanshSized + 'static> fmt::Display for LamanshDump<'a, 'b, NC, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let lamansh = self.lamansh;
+		let (n_c_sized, n_sized) = (NC::byted(), N::byted());
+
+		write!(f, "{:08x}  count     ", 0)?;
+		write_hex(f, &lamansh.array[.. n_c_sized])?;
+		writeln!(f, "  ({} elements)", lamansh.len())?;
+
+		let mut n = n_c_sized;
+		for (i, a) in lamansh.value_head_array.chunks(n_sized).enumerate() {
+			write!(f, "{:08x}  len[{}]    ", n, i)?;
+			write_hex(f, a)?;
+			writeln!(f, "  ({})", N::read_usize(a))?;
+			n += n_sized;
+		}
+
+		for (i, a) in lamansh.clone().into_iter().enumerate() {
+			let a = match a {
+				Ok(a) => a,
+				Err(e) => return writeln!(f, "{:08x}  value[{}]  {:?}", n, i, e),
+			};
+
+			write!(f, "{:08x}  value[{}]  ", n, i)?;
+			let shown = &a[.. ::std::cmp::min(a.len(), self.limit)];
+			write_hex(f, shown)?;
+			write!(f, "  {:?}", String::from_utf8_lossy(shown))?;
+			if shown.len() < a.len() {
+				write!(f, " ... ({} bytes)", a.len())?;
+			}
+			writeln!(f)?;
+			n += a.len();
+		}
+
+		if n < lamansh.array.len() {
+			writeln!(f, "{:08x}  {} bytes after the values", n, lamansh.array.len() - n)?;
+		}
+
+		Ok( () )
+	}
+}
+
+
+///Value as a byte string `b"..."`
+struct LamanshDebugValue<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for LamanshDebugValue<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "b\"")?;
+		for a in self.0.iter() {
+			for a in ascii::escape_default(*a) {
+				write!(f, "{}", a as char)?;
+			}
+		}
+		write!(f, "\"")
+	}
+}
+
+///`{:?}` - the slices of the handler, `{:#?}` - the list of the values.
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> fmt::Debug for cluLamansh<'a, NC, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !f.alternate() {
+			return f.debug_struct("cluLamansh")
+				.field("array", &self.array)
+				.field("value_head_array", &self.value_head_array)
+				.field("value_array", &self.value_array)
+				.finish();
+		}
+
+		let mut list = f.debug_list();
+		for a in self.clone().into_iter() {
+			match a {
+				Ok(a) => list.entry(&LamanshDebugValue(a)),
+				Err(e) => list.entry(&e),
+			};
+		}
+		list.finish()
+	}
+}
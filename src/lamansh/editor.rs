@@ -0,0 +1,203 @@
+/*!
+Inserting, removing and replacing elements of the protocol without rebuilding it.
+
+Only the headers and values after the changed element are moved.
+ */
+
+use std::marker::PhantomData;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+use lamansh::header_len;
+
+///Editor of the protocol in the buffer (`Vec<u8>` or `LamanshBuffer`).
+#[derive(Debug)]
+pub struct LamanshEditor<'b, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	array: &'b mut Vec<u8>,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'b, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshEditor<'b, NC, N> {
+	///Create an editor, all elements must be within the values.
+	pub fn new(array: &'b mut Vec<u8>) -> Result< Self, cluLamanshErr > {
+		let n = header_len::<NC, N>(array)?;
+
+		let mut len_n = 0usize;
+		for a in array[NC::byted() .. n].chunks(N::byted()) {
+			len_n = match len_n.checked_add(N::read_usize(a)) {
+				Some(a) => a,
+				_ => return Err( cluLamanshErr::ErrGetNextValue ),
+			};
+		}
+		if len_n > array.len() - n {
+			return Err( cluLamanshErr::ErrGetNextValue );
+		}
+
+		Ok(
+			Self {
+				array: array,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		NC::read_usize(&self.array[.. NC::byted()]) + 1
+	}
+
+	#[inline]
+	pub fn as_lamansh(&self) -> cluLamansh<NC, N> {
+		cluLamansh::array(self.array).unwrap()
+	}
+
+	///Position of the value header of the element
+	#[inline]
+	fn head_n(&self, index: usize) -> usize {
+		NC::byted() + N::byted() * index
+	}
+
+	#[inline]
+	fn value_len(&self, index: usize) -> usize {
+		let n = self.head_n(index);
+		N::read_usize(&self.array[n .. n + N::byted()])
+	}
+
+	///Position of the value of the element
+	#[inline]
+	fn value_n(&self, index: usize) -> usize {
+		self.head_n(self.len()) + (0 .. index).map(|a| self.value_len(a)).sum::<usize>()
+	}
+
+	#[inline]
+	fn write_len(&mut self, len: usize) {
+		NC::write_usize(len - 1, &mut self.array[.. NC::byted()]);
+	}
+
+	///Add the element to the end
+	#[inline]
+	pub fn push(&mut self, array: &[u8]) -> Result< (), ToLamanshErr > {
+		let len = self.len();
+		self.insert(len, array)
+	}
+
+	pub fn insert(&mut self, index: usize, array: &[u8]) -> Result< (), ToLamanshErr > {
+		let len = self.len();
+		if index > len {
+			return Err( ToLamanshErr::IndexOverflow );
+		}
+		if len + 1 > NC::max_value() {
+			return Err( ToLamanshErr::CountOverflow );
+		}
+		if array.len() > N::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+
+		let n_sized = N::byted();
+		let head_n = self.head_n(index);
+		let value_n = self.value_n(index);
+		let end = self.array.len();
+
+		//[head_n .. value_n] moves by the header, [value_n .. end] by the header and the value
+		self.array.resize(end + n_sized + array.len(), 0);
+		self.array.copy_within(value_n .. end, value_n + n_sized + array.len());
+		self.array.copy_within(head_n .. value_n, head_n + n_sized);
+
+		N::write_usize(array.len(), &mut self.array[head_n .. head_n + n_sized]);
+		self.array[value_n + n_sized .. value_n + n_sized + array.len()].copy_from_slice(array);
+		self.write_len(len + 1);
+
+		Ok( () )
+	}
+
+	///Remove the element, the last element cannot be removed
+	pub fn remove(&mut self, index: usize) -> Result< (), ToLamanshErr > {
+		let len = self.len();
+		if index >= len {
+			return Err( ToLamanshErr::IndexOverflow );
+		}
+		if len == 1 {
+			return Err( ToLamanshErr::EmptyArray );
+		}
+
+		let n_sized = N::byted();
+		let head_n = self.head_n(index);
+		let value_n = self.value_n(index);
+		let value_len = self.value_len(index);
+		let end = self.array.len();
+
+		self.array.copy_within(head_n + n_sized .. value_n, head_n);
+		self.array.copy_within(value_n + value_len .. end, value_n - n_sized);
+		self.array.truncate(end - n_sized - value_len);
+		self.write_len(len - 1);
+
+		Ok( () )
+	}
+
+	///Replace the value of the element, the len may differ
+	pub fn replace(&mut self, index: usize, array: &[u8]) -> Result< (), ToLamanshErr > {
+		if index >= self.len() {
+			return Err( ToLamanshErr::IndexOverflow );
+		}
+		if array.len() > N::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+
+		let head_n = self.head_n(index);
+		let value_n = self.value_n(index);
+		let value_len = self.value_len(index);
+		let end = self.array.len();
+
+		if array.len() > value_len {
+			self.array.resize(end + array.len() - value_len, 0);
+			self.array.copy_within(value_n + value_len .. end, value_n + array.len());
+		}else
+		if array.len() < value_len {
+			self.array.copy_within(value_n + value_len .. end, value_n + array.len());
+			self.array.truncate(end + array.len() - value_len);
+		}
+
+		N::write_usize(array.len(), &mut self.array[head_n .. head_n + N::byted()]);
+		self.array[value_n .. value_n + array.len()].copy_from_slice(array);
+
+		Ok( () )
+	}
+
+	///Swap two elements, only the values between them are moved
+	pub fn swap(&mut self, a: usize, b: usize) -> Result< (), ToLamanshErr > {
+		let len = self.len();
+		if a >= len || b >= len {
+			return Err( ToLamanshErr::IndexOverflow );
+		}
+		if a == b {
+			return Ok( () );
+		}
+		let (a, b) = if a < b { (a, b) } else { (b, a) };
+
+		let n_sized = N::byted();
+		let (head_a, head_b) = (self.head_n(a), self.head_n(b));
+		let (len_a, len_b) = (self.value_len(a), self.value_len(b));
+		let value_a = self.value_n(a);
+		let end_b = self.value_n(b) + len_b;
+
+		//A M B -> M B A -> B M A
+		{
+			let values = &mut self.array[value_a .. end_b];
+			values.rotate_left(len_a);
+
+			let m_len = values.len() - len_a - len_b;
+			values[.. m_len + len_b].rotate_left(m_len);
+		}
+
+		N::write_usize(len_b, &mut self.array[head_a .. head_a + n_sized]);
+		N::write_usize(len_a, &mut self.array[head_b .. head_b + n_sized]);
+
+		Ok( () )
+	}
+}
@@ -0,0 +1,313 @@
+/*!
+Optional envelope of the protocol: magic bytes, format version, profile (NC/N) and flags.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_lamansh_enveloped;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::envelope::LamanshEnvelope;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let body = [&b"TEST"[..], &b""[..]].to_lamansh::<U8, U16>().unwrap();
+	let array = LamanshEnvelope::new::<U8, U16>().wrap(&body);
+
+	let enveloped = new_lamansh_enveloped(&array).unwrap();
+	let lamansh = enveloped.lamansh::<U8, U16>().unwrap();
+
+	let mut iter = lamansh.iter();
+	assert_eq!(iter.next(), Some( Result::Ok( &b"TEST"[..] ) ));
+	assert_eq!(iter.next(), Some( Result::Ok( &b""[..] ) ));
+	assert_eq!(iter.next(), None);
+}
+```
+ */
+
+use lamansh::buffer::LamanshBuffer;
+use lamansh::sized::LamanshSized;
+use lamansh::sized::LamanshWidth;
+use lamansh::sized::U8;
+use lamansh::sized::U16;
+use lamansh::sized::U24;
+use lamansh::sized::U32;
+use lamansh::sized::U64;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+use std::borrow::Cow;
+use lamansh::checksum;
+use lamansh::codec::LamanshCodec;
+use lamansh::codec::LAMANSH_CODEC_IDENTITY;
+use lamansh::indexed::cluLamanshIndexed;
+use lamansh::dedup::cluLamanshDedup;
+
+pub const LAMANSH_MAGIC: [u8; 4] = [b'L', b'M', b'S', b'H'];
+pub const LAMANSH_VERSION: u8 = 1;
+
+///The protocol ends with the checksum trailer (`lamansh::checksum`)
+pub const LAMANSH_FLAG_CHECKSUM: u8 = 0b0000_0001;
+
+///The headers contain the ends of the values (`lamansh::indexed`)
+pub const LAMANSH_FLAG_INDEXED: u8 = 0b0000_0010;
+
+///Id of the codec of the body (`lamansh::codec`), 0 - the body is not encoded
+pub const LAMANSH_FLAG_CODEC: u8 = 0b0001_1100;
+const LAMANSH_FLAG_CODEC_SHIFT: u8 = 2;
+
+///The headers may refer to the values of the earlier elements (`lamansh::dedup`)
+pub const LAMANSH_FLAG_DEDUP: u8 = 0b0010_0000;
+
+///Flags known to this version, the envelope with other flags is not accepted
+pub const LAMANSH_FLAGS_KNOWN: u8 = LAMANSH_FLAG_CHECKSUM | LAMANSH_FLAG_INDEXED | LAMANSH_FLAG_CODEC | LAMANSH_FLAG_DEDUP;
+
+
+///Magic bytes (4), version (1), profile (1, NC byted << 4 | N byted), flags (1)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LamanshEnvelope {
+	pub version: u8,
+
+	pub count_width: LamanshWidth,
+	pub value_width: LamanshWidth,
+
+	pub flags: u8,
+}
+
+impl LamanshEnvelope {
+	///Envelope len
+	pub const LEN: usize = 7;
+
+	#[inline]
+	pub fn new<NC: LamanshSized + 'static, N: LamanshSized + 'static>() -> Self {
+		Self::width(NC::width(), N::width())
+	}
+
+	#[inline]
+	pub fn width(count_width: LamanshWidth, value_width: LamanshWidth) -> Self {
+		Self {
+			version: LAMANSH_VERSION,
+
+			count_width: count_width,
+			value_width: value_width,
+
+			flags: 0,
+		}
+	}
+
+	#[inline]
+	pub fn with_flags(mut self, flags: u8) -> Self {
+		self.flags = flags;
+		self
+	}
+
+	///Id of the codec of the body
+	#[inline]
+	pub fn codec(&self) -> u8 {
+		(self.flags & LAMANSH_FLAG_CODEC) >> LAMANSH_FLAG_CODEC_SHIFT
+	}
+
+	///`id` must not be greater than `LAMANSH_CODEC_MAX`
+	#[inline]
+	pub fn with_codec(mut self, id: u8) -> Self {
+		self.flags = (self.flags & !LAMANSH_FLAG_CODEC) | ((id << LAMANSH_FLAG_CODEC_SHIFT) & LAMANSH_FLAG_CODEC);
+		self
+	}
+
+	#[inline]
+	pub fn is_profile<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self) -> bool {
+		self.count_width == NC::width() && self.value_width == N::width()
+	}
+
+	///Reading and checking the envelope
+	pub fn read(array: &[u8]) -> Result<Self, cluLamanshErr> {
+		let array = match array.get(.. Self::LEN) {
+			Some(a) => a,
+			_ => return Err( cluLamanshErr::ErrGetEnvelope ),
+		};
+		if array[.. 4] != LAMANSH_MAGIC {
+			return Err( cluLamanshErr::ErrEnvelopeMagic );
+		}
+		if array[4] != LAMANSH_VERSION {
+			return Err( cluLamanshErr::ErrEnvelopeVersion );
+		}
+
+		let (count_width, value_width) = match (
+			LamanshWidth::from_byted( (array[5] >> 4) as usize ),
+			LamanshWidth::from_byted( (array[5] & 0x0F) as usize ),
+		) {
+			(Some(a), Some(b)) => (a, b),
+			_ => return Err( cluLamanshErr::ErrEnvelopeProfile ),
+		};
+
+		let flags = array[6];
+		if flags & !LAMANSH_FLAGS_KNOWN != 0 {
+			return Err( cluLamanshErr::ErrEnvelopeFlags );
+		}
+
+		Ok(
+			Self {
+				version: LAMANSH_VERSION,
+
+				count_width: count_width,
+				value_width: value_width,
+
+				flags: flags,
+			}
+		)
+	}
+
+	///Writing the envelope, `array` len must be `LamanshEnvelope::LEN`
+	pub fn write(&self, array: &mut [u8]) {
+		array[.. 4].copy_from_slice(&LAMANSH_MAGIC);
+		array[4] = self.version;
+		array[5] = ((self.count_width.byted() as u8) << 4) | self.value_width.byted() as u8;
+		array[6] = self.flags;
+	}
+
+	///Envelope and protocol
+	pub fn wrap(&self, lamansh: &[u8]) -> Vec<u8> {
+		let mut buffer = LamanshBuffer::with_capacity(Self::LEN + lamansh.len());
+		self.update_buffer(lamansh, &mut buffer);
+
+		buffer.into()
+	}
+
+	///Envelope and the protocol encoded with the codec
+	pub fn wrap_codec<C: LamanshCodec>(&self, codec: &C, lamansh: &[u8]) -> Vec<u8> {
+		self.with_codec(codec.id()).wrap(&codec.encode(lamansh))
+	}
+
+	///Envelope and protocol. Using an external buffer.
+	pub fn update_buffer(&self, lamansh: &[u8], vec_result: &mut LamanshBuffer) {
+		vec_result.set_len(Self::LEN + lamansh.len());
+		self.write(&mut vec_result[.. Self::LEN]);
+		vec_result[Self::LEN ..].copy_from_slice(lamansh);
+	}
+}
+
+
+///Checked envelope and the protocol after it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LamanshEnveloped<'a> {
+	envelope: LamanshEnvelope,
+	array: &'a [u8],
+}
+
+impl<'a> LamanshEnveloped<'a> {
+	pub fn new(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		let envelope = LamanshEnvelope::read(array)?;
+
+		let mut array = &array[LamanshEnvelope::LEN ..];
+		if envelope.flags & LAMANSH_FLAG_CHECKSUM != 0 {
+			array = checksum::split_checksum(array)?;
+		}
+
+		Ok(
+			Self {
+				envelope: envelope,
+				array: array,
+			}
+		)
+	}
+
+	#[inline]
+	pub fn envelope(&self) -> &LamanshEnvelope {
+		&self.envelope
+	}
+
+	///Protocol without the envelope and the checksum trailer
+	#[inline]
+	pub fn as_slice(&self) -> &'a [u8] {
+		self.array
+	}
+
+	#[inline]
+	pub fn is_indexed(&self) -> bool {
+		self.envelope.flags & LAMANSH_FLAG_INDEXED != 0
+	}
+
+	#[inline]
+	pub fn is_dedup(&self) -> bool {
+		self.envelope.flags & LAMANSH_FLAG_DEDUP != 0
+	}
+
+	///Body decoded with the codec, the body without the codec is not copied.
+	pub fn decode_body<C: LamanshCodec>(&self, codec: &C) -> Result<Cow<'a, [u8]>, cluLamanshErr> {
+		match self.envelope.codec() {
+			LAMANSH_CODEC_IDENTITY => Ok( Cow::Borrowed(self.array) ),
+			a if a == codec.id() => Ok( Cow::Owned(codec.decode(self.array)?) ),
+			_ => Err( cluLamanshErr::ErrCodec ),
+		}
+	}
+
+	///Create a handler, the profile must match the envelope.
+	pub fn lamansh<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self) -> Result<cluLamansh<'a, NC, N>, cluLamanshErr> {
+		if !self.envelope.is_profile::<NC, N>() {
+			return Err( cluLamanshErr::ErrEnvelopeProfile );
+		}
+		if self.is_indexed() || self.is_dedup() || self.envelope.codec() != LAMANSH_CODEC_IDENTITY {
+			return Err( cluLamanshErr::ErrEnvelopeFlags );
+		}
+		cluLamansh::new(self.array)
+	}
+
+	///Create a handler of the indexed protocol, the profile must match the envelope.
+	pub fn lamansh_indexed<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self) -> Result<cluLamanshIndexed<'a, NC, N>, cluLamanshErr> {
+		if !self.envelope.is_profile::<NC, N>() {
+			return Err( cluLamanshErr::ErrEnvelopeProfile );
+		}
+		if !self.is_indexed() || self.is_dedup() || self.envelope.codec() != LAMANSH_CODEC_IDENTITY {
+			return Err( cluLamanshErr::ErrEnvelopeFlags );
+		}
+		cluLamanshIndexed::new(self.array)
+	}
+
+	///Create a handler of the deduplicating protocol, the profile must match the envelope.
+	pub fn lamansh_dedup<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self) -> Result<cluLamanshDedup<'a, NC, N>, cluLamanshErr> {
+		if !self.envelope.is_profile::<NC, N>() {
+			return Err( cluLamanshErr::ErrEnvelopeProfile );
+		}
+		if !self.is_dedup() || self.is_indexed() || self.envelope.codec() != LAMANSH_CODEC_IDENTITY {
+			return Err( cluLamanshErr::ErrEnvelopeFlags );
+		}
+		cluLamanshDedup::new(self.array)
+	}
+
+	///Create a handler with the profile from the envelope, the protocol must not be indexed, deduplicated or encoded.
+	pub fn visit<V: LamanshVisitor<'a>>(&self, visitor: V) -> Result<V::Output, cluLamanshErr> {
+		if self.is_indexed() || self.is_dedup() || self.envelope.codec() != LAMANSH_CODEC_IDENTITY {
+			return Err( cluLamanshErr::ErrEnvelopeFlags );
+		}
+		visit_width(self.array, self.envelope.count_width, self.envelope.value_width, visitor)
+	}
+}
+
+///Create a handler with the profile selected at runtime.
+pub(crate) fn visit_width<'a, V: LamanshVisitor<'a>>(array: &'a [u8], count_width: LamanshWidth, value_width: LamanshWidth, visitor: V) -> Result<V::Output, cluLamanshErr> {
+	match count_width {
+		LamanshWidth::U8 => visit_value::<U8, V>(array, value_width, visitor),
+		LamanshWidth::U16 => visit_value::<U16, V>(array, value_width, visitor),
+		LamanshWidth::U24 => visit_value::<U24, V>(array, value_width, visitor),
+		LamanshWidth::U32 => visit_value::<U32, V>(array, value_width, visitor),
+		LamanshWidth::U64 => visit_value::<U64, V>(array, value_width, visitor),
+	}
+}
+
+fn visit_value<'a, NC: LamanshSized + 'static, V: LamanshVisitor<'a>>(array: &'a [u8], value_width: LamanshWidth, visitor: V) -> Result<V::Output, cluLamanshErr> {
+	Ok(
+		match value_width {
+			LamanshWidth::U8 => visitor.visit(cluLamansh::<NC, U8>::new(array)?),
+			LamanshWidth::U16 => visitor.visit(cluLamansh::<NC, U16>::new(array)?),
+			LamanshWidth::U24 => visitor.visit(cluLamansh::<NC, U24>::new(array)?),
+			LamanshWidth::U32 => visitor.visit(cluLamansh::<NC, U32>::new(array)?),
+			LamanshWidth::U64 => visitor.visit(cluLamansh::<NC, U64>::new(array)?),
+		}
+	)
+}
+
+///Handler of the protocol with the profile selected at runtime.
+pub trait LamanshVisitor<'a> {
+	type Output;
+
+	fn visit<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self, lamansh: cluLamansh<'a, NC, N>) -> Self::Output;
+}
@@ -0,0 +1,117 @@
+/*!
+Random access to the elements of the protocol in `Read + Seek` without reading the values.
+
+Only the headers are read, the values are read after seeking to them.
+ */
+
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamanshErr;
+use lamansh::invalid_data;
+
+#[derive(Debug)]
+pub struct LamanshFile<R: Read + Seek, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	read: R,
+
+	///Position of the values in `read`
+	value_n: u64,
+	///Start of every value and the end of the last value, relative to `value_n`
+	offsets: Vec<u64>,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<R: Read + Seek, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshFile<R, NC, N> {
+	///Read the headers starting from the current position, all elements must be within `read`.
+	pub fn new(mut read: R) -> io::Result<Self> {
+		let start = read.seek(SeekFrom::Current(0))?;
+
+		let mut array = vec![0u8; NC::byted()];
+		read.read_exact(&mut array)?;
+		let count = match (NC::read_usize(&array) as u64).checked_add(1) {
+			Some(a) => a,
+			_ => return Err( invalid_data(cluLamanshErr::ErrGetValueHead) ),
+		};
+
+		let head_len = match count.checked_mul(N::byted() as u64) {
+			Some(a) if a <= usize::max_value() as u64 => a,
+			_ => return Err( invalid_data(cluLamanshErr::ErrGetValueHead) ),
+		};
+		array.clear();
+		(&mut read).take(head_len).read_to_end(&mut array)?;
+		if array.len() as u64 != head_len {
+			return Err( invalid_data(cluLamanshErr::ErrGetValueHead) );
+		}
+
+		let mut offsets = Vec::with_capacity(count as usize + 1);
+		let mut len_n = 0u64;
+		offsets.push(len_n);
+		for a in array.chunks(N::byted()) {
+			len_n = match len_n.checked_add(N::read_usize(a) as u64) {
+				Some(a) => a,
+				_ => return Err( invalid_data(cluLamanshErr::ErrGetNextValue) ),
+			};
+			offsets.push(len_n);
+		}
+
+		let value_n = start + NC::byted() as u64 + head_len;
+		let end = read.seek(SeekFrom::End(0))?;
+		if end < value_n || end - value_n < len_n {
+			return Err( invalid_data(cluLamanshErr::ErrGetNextValue) );
+		}
+
+		Ok(
+			Self {
+				read: read,
+
+				value_n: value_n,
+				offsets: offsets,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.offsets.len() - 1
+	}
+
+	#[inline]
+	pub fn value_len(&self, index: usize) -> Option<u64> {
+		match (self.offsets.get(index), self.offsets.get(index + 1)) {
+			(Some(a), Some(b)) => Some( b - a ),
+			_ => None,
+		}
+	}
+
+	///Reader of the value of the element
+	pub fn value_reader<'r>(&'r mut self, index: usize) -> io::Result<impl Read + 'r> {
+		let len = match self.value_len(index) {
+			Some(a) => a,
+			_ => return Err( io::Error::new(io::ErrorKind::InvalidInput, format!("{:?}", cluLamanshErr::ErrGetIndex)) ),
+		};
+		self.read.seek(SeekFrom::Start(self.value_n + self.offsets[index]))?;
+
+		Ok( (&mut self.read).take(len) )
+	}
+
+	pub fn read_value(&mut self, index: usize) -> io::Result<Vec<u8>> {
+		let mut array = Vec::new();
+		self.value_reader(index)?.read_to_end(&mut array)?;
+
+		Ok( array )
+	}
+
+	#[inline]
+	pub fn into_inner(self) -> R {
+		self.read
+	}
+}
@@ -0,0 +1,178 @@
+/*!
+Indexed variant of the protocol (`LAMANSH_FLAG_INDEXED` in the envelope).
+
+Instead of the len of every element the headers contain the end of every value relative to the start of the values,
+so the element is found with two reads and the element of the byte is found with a binary search.
+The sum of the values must fit into `N`.
+ */
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+use lamansh::header_len;
+
+///Build the indexed protocol from the values.
+pub fn to_lamansh_indexed<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]>>(array: &[A]) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut buffer = LamanshBuffer::new();
+	update_buffer_indexed::<NC, N, A>(array, &mut buffer)?;
+
+	Ok( buffer.into() )
+}
+
+///Build the indexed protocol from the values. Using an external buffer.
+pub fn update_buffer_indexed<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]>>(array: &[A], vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+	let len_elements = array.len();
+	if len_elements == 0 {
+		return Err( ToLamanshErr::EmptyArray );
+	}
+	if len_elements > NC::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+
+	let mut len_n = 0usize;
+	for a in array.iter() {
+		len_n = match len_n.checked_add(a.as_ref().len()) {
+			Some(a) if a <= N::max_value() => a,
+			_ => return Err( ToLamanshErr::ValueOverflow ),
+		};
+	}
+
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+	vec_result.set_len(n_c_sized + (n_sized * len_elements) + len_n);
+
+	NC::write_usize(len_elements-1, &mut vec_result[.. n_c_sized]);
+
+	let mut n = n_c_sized;
+	let values_n = n + ( n_sized * len_elements );
+	let mut end = 0;
+	for a in array.iter() {
+		let a = a.as_ref();
+		vec_result[values_n + end .. values_n + end + a.len()].copy_from_slice(a);
+		end += a.len();
+
+		N::write_usize(end, &mut vec_result[n .. n + n_sized]);
+		n += n_sized;
+	}
+
+	Ok( () )
+}
+
+///Convert the classic protocol (lengths of the elements) into the indexed one.
+pub fn indexed_from_lamansh<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(lamansh: &cluLamansh<'a, NC, N>) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut array = Vec::with_capacity(lamansh.len());
+	for a in lamansh.iter() {
+		match a {
+			Ok(a) => array.push(a),
+			Err(_) => return Err( ToLamanshErr::InvalidLamansh ),
+		}
+	}
+
+	to_lamansh_indexed::<NC, N, &[u8]>(&array)
+}
+
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshIndexed<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	value_head_array: &'a [u8],
+	value_array: &'a [u8],
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshIndexed<'a, NC, N> {
+	///Create a handler, the ends must not decrease and must be within the values.
+	pub fn new(array: &'a [u8]) -> Result< Self, cluLamanshErr > {
+		let n = header_len::<NC, N>(array)?;
+		let value_head_array = &array[NC::byted() .. n];
+		let value_array = &array[n ..];
+
+		let mut end = 0;
+		for a in value_head_array.chunks(N::byted()) {
+			let next = N::read_usize(a);
+			if next < end {
+				return Err( cluLamanshErr::ErrGetNextValue );
+			}
+			end = next;
+		}
+		if end > value_array.len() {
+			return Err( cluLamanshErr::ErrGetNextValue );
+		}
+
+		Ok(
+			Self {
+				value_head_array: value_head_array,
+				value_array: value_array,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.value_head_array.len() / N::byted()
+	}
+
+	#[inline]
+	fn end(&self, index: usize) -> usize {
+		let n = index * N::byted();
+		N::read_usize(&self.value_head_array[n .. n + N::byted()])
+	}
+
+	///Position of the value of the element relative to the start of the values
+	pub fn value_range(&self, index: usize) -> Option<Range<usize>> {
+		if index >= self.len() {
+			return None;
+		}
+		let start = match index {
+			0 => 0,
+			a => self.end(a - 1),
+		};
+
+		Some( start .. self.end(index) )
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+		let array: &'a [u8] = self.value_array;
+		self.value_range(index).map(|a| &array[a])
+	}
+
+	///Element containing the byte of the values, empty elements are skipped
+	pub fn index_of(&self, n: usize) -> Option<usize> {
+		let (mut start, mut end) = (0, self.len());
+		while start < end {
+			let mid = start + (end - start) / 2;
+			if self.end(mid) > n {
+				end = mid;
+			}else {
+				start = mid + 1;
+			}
+		}
+
+		match start < self.len() {
+			true => Some( start ),
+			false => None,
+		}
+	}
+
+	#[inline]
+	pub fn iter<'b>(&'b self) -> impl Iterator<Item = &'a [u8]> + 'b {
+		(0 .. self.len()).map(move |a| self.get(a).unwrap())
+	}
+
+	///Convert into the classic protocol
+	pub fn to_lamansh(&self) -> Result<Vec<u8>, ToLamanshErr> {
+		self.iter().collect::<Vec<_>>().to_lamansh::<NC, N>()
+	}
+}
@@ -0,0 +1,543 @@
+/*!
+Conversion of the protocol to JSON and back without external dependencies.
+
+The message is a JSON array, every element is one of:
+* string - the value is UTF-8 text without control characters (except `\t`, `\n`, `\r`);
+* `{"map": {"name": element, ...}}` - the value is a nested message (same profile) of pairs name, value with unique UTF-8 names;
+* `{"lamansh": [element, ...]}` - the value is a nested message of the same profile;
+* `{"base64": "..."}` or `{"hex": "..."}` - any other value.
+
+The conversion back gives the same bytes.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::json::to_json;
+use cluLamansh::lamansh::json::from_json;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let array = [&b"text"[..], &[0xFF, 0x00][..]].to_lamansh::<U8, U16>().unwrap();
+	let json = to_json(&new_custom_lamansh::<U8, U16>(&array).unwrap()).unwrap();
+
+	assert_eq!(json, r#"["text",{"base64":"/wA="}]"#);
+	assert_eq!(from_json::<U8, U16>(&json).unwrap(), array);
+}
+```
+ */
+
+use std::char;
+use std::str;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Depth of nested messages and JSON arrays and objects
+pub const LAMANSH_JSON_DEPTH: usize = 32;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshJsonBinary {
+	Base64,
+	Hex,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LamanshJsonErr {
+	///Invalid JSON, position of the error in bytes
+	Syntax(usize),
+
+	///The JSON value is not an element of the protocol
+	Element,
+
+	///Invalid string `hex` or `base64`
+	Binary,
+
+	///The nesting is deeper than `LAMANSH_JSON_DEPTH`
+	Depth,
+
+	///The message or the nested message is damaged
+	Lamansh(cluLamanshErr),
+
+	///Error of building the message or the nested message
+	Build(ToLamanshErr),
+}
+
+///Settings of the conversion to JSON.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LamanshJson {
+	binary: LamanshJsonBinary,
+	nested: bool,
+}
+
+impl LamanshJson {
+	///Base64 and the detection of nested messages
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			binary: LamanshJsonBinary::Base64,
+			nested: true,
+		}
+	}
+
+	#[inline]
+	pub fn with_binary(mut self, binary: LamanshJsonBinary) -> Self {
+		self.binary = binary;
+		self
+	}
+
+	///Detection of nested messages and maps
+	#[inline]
+	pub fn with_nested(mut self, nested: bool) -> Self {
+		self.nested = nested;
+		self
+	}
+
+	pub fn to_json<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, lamansh: &cluLamansh<'a, NC, N>) -> Result<String, LamanshJsonErr> {
+		let mut result = String::new();
+		self.write_array(lamansh, 0, &mut result)?;
+
+		Ok( result )
+	}
+
+	fn write_array<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, lamansh: &cluLamansh<'a, NC, N>, depth: usize, result: &mut String) -> Result<(), LamanshJsonErr> {
+		result.push('[');
+		for (i, a) in lamansh.clone().into_iter().enumerate() {
+			if i != 0 {
+				result.push(',');
+			}
+			self.write_value::<NC, N>(a.map_err(LamanshJsonErr::Lamansh)?, depth, result)?;
+		}
+		result.push(']');
+
+		Ok( () )
+	}
+
+	fn write_value<NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, array: &[u8], depth: usize, result: &mut String) -> Result<(), LamanshJsonErr> {
+		if let Some(a) = text(array) {
+			write_string(a, result);
+			return Ok( () );
+		}
+
+		//the values of the nested message are 2 JSON levels deeper (object, array),
+		//the string of the binary value is 1 more, the parser limits the JSON levels
+		if self.nested && (depth + 2) * 2 < LAMANSH_JSON_DEPTH {
+			if let Some(lamansh) = nested::<NC, N>(array) {
+				match map_names(&lamansh) {
+					Some(names) => {
+						result.push_str("{\"map\":{");
+						let values = lamansh.clone().into_iter().skip(1).step_by(2);
+						for (i, (name, a)) in names.into_iter().zip(values).enumerate() {
+							if i != 0 {
+								result.push(',');
+							}
+							write_string(name, result);
+							result.push(':');
+							self.write_value::<NC, N>(a.map_err(LamanshJsonErr::Lamansh)?, depth + 1, result)?;
+						}
+						result.push_str("}}");
+					},
+					None => {
+						result.push_str("{\"lamansh\":");
+						self.write_array(&lamansh, depth + 1, result)?;
+						result.push('}');
+					},
+				}
+				return Ok( () );
+			}
+		}
+
+		match self.binary {
+			LamanshJsonBinary::Base64 => {
+				result.push_str("{\"base64\":\"");
+				encode_base64(array, result);
+			},
+			LamanshJsonBinary::Hex => {
+				result.push_str("{\"hex\":\"");
+				for a in array.iter() {
+					result.push_str(&format!("{:02x}", a));
+				}
+			},
+		}
+		result.push_str("\"}");
+
+		Ok( () )
+	}
+}
+
+impl Default for LamanshJson {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+///JSON of the message with the settings `LamanshJson::new()`
+#[inline]
+pub fn to_json<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(lamansh: &cluLamansh<'a, NC, N>) -> Result<String, LamanshJsonErr> {
+	LamanshJson::new().to_json(lamansh)
+}
+
+///Values of the message from JSON, nested messages are built with the same profile.
+pub fn json_to_values<NC: LamanshSized + 'static, N: LamanshSized + 'static>(json: &str) -> Result<Vec<Vec<u8>>, LamanshJsonErr> {
+	let mut parser = JsonParser {
+		array: json.as_bytes(),
+		n: 0,
+	};
+	let value = parser.parse_value(0)?;
+	parser.skip_space();
+	if parser.n != parser.array.len() {
+		return Err( LamanshJsonErr::Syntax(parser.n) );
+	}
+
+	match value {
+		JsonValue::Array(array) => array.into_iter().map(|a| element::<NC, N>(a)).collect(),
+		_ => Err( LamanshJsonErr::Element ),
+	}
+}
+
+///Message from JSON
+#[inline]
+pub fn from_json<NC: LamanshSized + 'static, N: LamanshSized + 'static>(json: &str) -> Result<Vec<u8>, LamanshJsonErr> {
+	json_to_values::<NC, N>(json)?.to_lamansh::<NC, N>().map_err(LamanshJsonErr::Build)
+}
+
+
+///Value as text if it is UTF-8 without control characters
+fn text(array: &[u8]) -> Option<&str> {
+	let a = str::from_utf8(array).ok()?;
+	match a.chars().any(|a| a.is_control() && a != '\t' && a != '\n' && a != '\r') {
+		true => None,
+		false => Some( a ),
+	}
+}
+
+///Value as a nested message if the message takes all the value and can be built again
+fn nested<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(array: &'a [u8]) -> Option<cluLamansh<'a, NC, N>> {
+	let lamansh = cluLamansh::<NC, N>::new(array).ok()?;
+	//the count header allows one element more than `to_lamansh`
+	if lamansh.len() > NC::max_value() {
+		return None;
+	}
+	match lamansh.values_len() {
+		Some(a) if a == lamansh.value_array.len() => Some( lamansh ),
+		_ => None,
+	}
+}
+
+///Names of the nested message of pairs name, value
+fn map_names<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(lamansh: &cluLamansh<'a, NC, N>) -> Option<Vec<&'a str>> {
+	if lamansh.len() % 2 != 0 {
+		return None;
+	}
+
+	let mut names: Vec<&'a str> = Vec::with_capacity(lamansh.len() / 2);
+	for a in lamansh.clone().into_iter().step_by(2) {
+		let a = text(a.ok()?)?;
+		if names.contains(&a) {
+			return None;
+		}
+		names.push(a);
+	}
+
+	Some( names )
+}
+
+fn element<NC: LamanshSized + 'static, N: LamanshSized + 'static>(value: JsonValue) -> Result<Vec<u8>, LamanshJsonErr> {
+	let mut object = match value {
+		JsonValue::String(a) => return Ok( a.into_bytes() ),
+		JsonValue::Object(a) => a,
+		_ => return Err( LamanshJsonErr::Element ),
+	};
+	if object.len() != 1 {
+		return Err( LamanshJsonErr::Element );
+	}
+
+	let (tag, value) = object.pop().unwrap();
+	match (tag.as_str(), value) {
+		("base64", JsonValue::String(a)) => decode_base64(&a),
+		("hex", JsonValue::String(a)) => decode_hex(&a),
+		("lamansh", JsonValue::Array(array)) => {
+			let values = array.into_iter().map(|a| element::<NC, N>(a)).collect::<Result<Vec<_>, _>>()?;
+			values.to_lamansh::<NC, N>().map_err(LamanshJsonErr::Build)
+		},
+		("map", JsonValue::Object(object)) => {
+			let mut values = Vec::with_capacity(object.len() * 2);
+			for (name, a) in object.into_iter() {
+				values.push(name.into_bytes());
+				values.push(element::<NC, N>(a)?);
+			}
+			values.to_lamansh::<NC, N>().map_err(LamanshJsonErr::Build)
+		},
+		_ => Err( LamanshJsonErr::Element ),
+	}
+}
+
+
+fn write_string(a: &str, result: &mut String) {
+	result.push('"');
+	for a in a.chars() {
+		match a {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			a if (a as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", a as u32)),
+			a => result.push(a),
+		}
+	}
+	result.push('"');
+}
+
+fn encode_base64(array: &[u8], result: &mut String) {
+	for a in array.chunks(3) {
+		let n = (a[0] as u32) << 16 | (*a.get(1).unwrap_or(&0) as u32) << 8 | *a.get(2).unwrap_or(&0) as u32;
+		for i in 0 .. 4 {
+			match i <= a.len() {
+				true => result.push(BASE64[(n >> (18 - 6 * i) & 0x3F) as usize] as char),
+				false => result.push('='),
+			}
+		}
+	}
+}
+
+fn decode_base64(a: &str) -> Result<Vec<u8>, LamanshJsonErr> {
+	let a = a.as_bytes();
+	if a.len() % 4 != 0 {
+		return Err( LamanshJsonErr::Binary );
+	}
+
+	let mut result = Vec::with_capacity(a.len() / 4 * 3);
+	for (i, chunk) in a.chunks(4).enumerate() {
+		let last = i == a.len() / 4 - 1;
+		let mut n = 0u32;
+		let mut len = 0;
+		for (j, b) in chunk.iter().enumerate() {
+			let value = match *b {
+				b'=' if last && j >= 2 => None,
+				b => match BASE64.iter().position(|a| *a == b) {
+					Some(a) if len == j => Some( a as u32 ),
+					_ => return Err( LamanshJsonErr::Binary ),
+				},
+			};
+			if let Some(value) = value {
+				len += 1;
+				n |= value << (18 - 6 * j);
+			}
+		}
+		if len < 2 {
+			return Err( LamanshJsonErr::Binary );
+		}
+
+		let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+		let bytes = &bytes[.. len - 1];
+		//unused bits must be zero, the encoding is canonical
+		if n & (0x00FF_FFFF >> (8 * (len - 1))) != 0 {
+			return Err( LamanshJsonErr::Binary );
+		}
+		result.extend_from_slice(bytes);
+	}
+
+	Ok( result )
+}
+
+fn decode_hex(a: &str) -> Result<Vec<u8>, LamanshJsonErr> {
+	let a = a.as_bytes();
+	if a.len() % 2 != 0 {
+		return Err( LamanshJsonErr::Binary );
+	}
+
+	let digit = |a: u8| (a as char).to_digit(16).ok_or(LamanshJsonErr::Binary);
+	a.chunks(2).map(|a| Ok( (digit(a[0])? << 4 | digit(a[1])?) as u8 )).collect()
+}
+
+
+enum JsonValue {
+	Null,
+	Bool,
+	Number,
+	String(String),
+	Array(Vec<JsonValue>),
+	Object(Vec<(String, JsonValue)>),
+}
+
+struct JsonParser<'a> {
+	array: &'a [u8],
+	n: usize,
+}
+
+impl<'a> JsonParser<'a> {
+	fn skip_space(&mut self) {
+		while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.array.get(self.n) {
+			self.n += 1;
+		}
+	}
+
+	#[inline]
+	fn err<T>(&self) -> Result<T, LamanshJsonErr> {
+		Err( LamanshJsonErr::Syntax(self.n) )
+	}
+
+	fn expect(&mut self, a: &[u8]) -> Result<(), LamanshJsonErr> {
+		match self.array[self.n ..].starts_with(a) {
+			true => {
+				self.n += a.len();
+				Ok( () )
+			},
+			false => self.err(),
+		}
+	}
+
+	fn parse_value(&mut self, depth: usize) -> Result<JsonValue, LamanshJsonErr> {
+		if depth >= LAMANSH_JSON_DEPTH {
+			return Err( LamanshJsonErr::Depth );
+		}
+		self.skip_space();
+
+		match self.array.get(self.n) {
+			Some(b'n') => self.expect(b"null").map(|_| JsonValue::Null),
+			Some(b't') => self.expect(b"true").map(|_| JsonValue::Bool),
+			Some(b'f') => self.expect(b"false").map(|_| JsonValue::Bool),
+			Some(b'"') => self.parse_string().map(JsonValue::String),
+			Some(b'[') => {
+				self.n += 1;
+				let mut array = Vec::new();
+				self.skip_space();
+				if self.array.get(self.n) == Some(&b']') {
+					self.n += 1;
+					return Ok( JsonValue::Array(array) );
+				}
+				loop {
+					array.push(self.parse_value(depth + 1)?);
+					self.skip_space();
+					match self.array.get(self.n) {
+						Some(b',') => self.n += 1,
+						Some(b']') => {
+							self.n += 1;
+							return Ok( JsonValue::Array(array) );
+						},
+						_ => return self.err(),
+					}
+				}
+			},
+			Some(b'{') => {
+				self.n += 1;
+				let mut object = Vec::new();
+				self.skip_space();
+				if self.array.get(self.n) == Some(&b'}') {
+					self.n += 1;
+					return Ok( JsonValue::Object(object) );
+				}
+				loop {
+					self.skip_space();
+					if self.array.get(self.n) != Some(&b'"') {
+						return self.err();
+					}
+					let name = self.parse_string()?;
+					self.skip_space();
+					self.expect(b":")?;
+					object.push((name, self.parse_value(depth + 1)?));
+					self.skip_space();
+					match self.array.get(self.n) {
+						Some(b',') => self.n += 1,
+						Some(b'}') => {
+							self.n += 1;
+							return Ok( JsonValue::Object(object) );
+						},
+						_ => return self.err(),
+					}
+				}
+			},
+			Some(b'-') | Some(b'0' ..= b'9') => {
+				let start = self.n;
+				while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E') | Some(b'0' ..= b'9') = self.array.get(self.n) {
+					self.n += 1;
+				}
+				match str::from_utf8(&self.array[start .. self.n]).ok().and_then(|a| a.parse::<f64>().ok()) {
+					Some(_) => Ok( JsonValue::Number ),
+					None => Err( LamanshJsonErr::Syntax(start) ),
+				}
+			},
+			_ => self.err(),
+		}
+	}
+
+	fn parse_hex4(&mut self) -> Result<u32, LamanshJsonErr> {
+		let a = match self.array.get(self.n .. self.n + 4).and_then(|a| str::from_utf8(a).ok()) {
+			Some(a) => a,
+			_ => return self.err(),
+		};
+		match u32::from_str_radix(a, 16) {
+			Ok(a) => {
+				self.n += 4;
+				Ok( a )
+			},
+			Err(_) => self.err(),
+		}
+	}
+
+	fn parse_string(&mut self) -> Result<String, LamanshJsonErr> {
+		self.n += 1;
+		let mut result = String::new();
+		loop {
+			let start = self.n;
+			while let Some(a) = self.array.get(self.n) {
+				match *a {
+					b'"' | b'\\' => break,
+					a if a < 0x20 => return self.err(),
+					_ => self.n += 1,
+				}
+			}
+			//the source is `str`, the boundaries are ASCII
+			result.push_str(str::from_utf8(&self.array[start .. self.n]).unwrap());
+
+			match self.array.get(self.n) {
+				Some(b'"') => {
+					self.n += 1;
+					return Ok( result );
+				},
+				Some(b'\\') => self.n += 1,
+				_ => return self.err(),
+			}
+
+			let escape = match self.array.get(self.n) {
+				Some(a) => *a,
+				_ => return self.err(),
+			};
+			self.n += 1;
+			match escape {
+				b'"' => result.push('"'),
+				b'\\' => result.push('\\'),
+				b'/' => result.push('/'),
+				b'b' => result.push('\u{8}'),
+				b'f' => result.push('\u{c}'),
+				b'n' => result.push('\n'),
+				b'r' => result.push('\r'),
+				b't' => result.push('\t'),
+				b'u' => {
+					let mut a = self.parse_hex4()?;
+					if a >= 0xD800 && a < 0xDC00 {
+						self.expect(b"\\u")?;
+						let b = self.parse_hex4()?;
+						if b < 0xDC00 || b >= 0xE000 {
+							return self.err();
+						}
+						a = 0x10000 + ((a - 0xD800) << 10) + (b - 0xDC00);
+					}
+					match char::from_u32(a) {
+						Some(a) => result.push(a),
+						None => return self.err(),
+					}
+				},
+				_ => return self.err(),
+			}
+		}
+	}
+}
@@ -0,0 +1,195 @@
+/*!
+Append-only file of Lamansh records with recovery after a torn write.
+
+Record: body len (4, Big Endian), body (the protocol), CRC-32C of the len and body (4, Big Endian).
+ */
+
+extern crate byteorder;
+use self::byteorder::BigEndian;
+use self::byteorder::ByteOrder;
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::Path;
+use lamansh::buffer::LamanshBuffer;
+use lamansh::checksum::Crc32c;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+use lamansh::invalid_data;
+
+///Len of the record without the body
+pub const LAMANSH_LOG_FRAME_LEN: usize = 8;
+
+#[derive(Debug)]
+pub struct LamanshLog {
+	file: File,
+	len: u64,
+
+	buffer: LamanshBuffer,
+}
+
+impl LamanshLog {
+	///Open or create the log. A damaged or partially written last record is truncated,
+	///a damaged record followed by a whole record is an `InvalidData` error and the file is not changed.
+	pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		let mut file = OpenOptions::new().read(true).write(true).create(true).open(path)?;
+
+		let mut array = Vec::new();
+		file.read_to_end(&mut array)?;
+
+		let mut len = 0;
+		loop {
+			match next_record(&array[len ..]) {
+				Some(Ok(a)) => len += a.len() + LAMANSH_LOG_FRAME_LEN,
+				//the whole record is in the file, the torn write can only be the last record
+				Some(Err(e @ cluLamanshErr::ChecksumMismatch { .. })) => {
+					let end = len + BigEndian::read_u32(&array[len ..]) as usize + LAMANSH_LOG_FRAME_LEN;
+					if end != array.len() {
+						return Err( invalid_data(e) );
+					}
+					break;
+				},
+				//the len of the record can be damaged, the torn write does not contain a whole record after it
+				Some(Err(e)) => {
+					if (len + 1 .. array.len()).any(|a| match next_record(&array[a ..]) { Some(Ok(_)) => true, _ => false }) {
+						return Err( invalid_data(e) );
+					}
+					break;
+				},
+				None => break,
+			}
+		}
+
+		let len = len as u64;
+		if len != array.len() as u64 {
+			file.set_len(len)?;
+		}
+		file.seek(SeekFrom::Start(len))?;
+
+		Ok(
+			Self {
+				file: file,
+				len: len,
+
+				buffer: LamanshBuffer::new(),
+			}
+		)
+	}
+
+	///Len of the records in bytes
+	#[inline]
+	pub fn len(&self) -> u64 {
+		self.len
+	}
+
+	///Write the record with one call to `write_all`.
+	pub fn append(&mut self, lamansh: &[u8]) -> io::Result<()> {
+		let len = lamansh.len();
+		if len > u32::max_value() as usize {
+			return Err( io::Error::new(io::ErrorKind::InvalidInput, "the record does not fit into the u32 len") );
+		}
+
+		self.buffer.set_len(len + LAMANSH_LOG_FRAME_LEN);
+		BigEndian::write_u32(&mut self.buffer[.. 4], len as u32);
+		self.buffer[4 .. 4 + len].copy_from_slice(lamansh);
+
+		let mut crc = Crc32c::new();
+		crc.update(&self.buffer[.. 4 + len]);
+		BigEndian::write_u32(&mut self.buffer[4 + len ..], crc.finish());
+
+		self.file.write_all(&self.buffer)?;
+		self.len += self.buffer.len() as u64;
+
+		Ok( () )
+	}
+
+	///Flush the records to the disk
+	#[inline]
+	pub fn sync(&self) -> io::Result<()> {
+		self.file.sync_data()
+	}
+
+	///Read all records, see `LamanshLogIter`.
+	pub fn read(&mut self) -> io::Result<Vec<u8>> {
+		let mut array = Vec::with_capacity(self.len as usize);
+
+		self.file.seek(SeekFrom::Start(0))?;
+		let result = (&mut self.file).take(self.len).read_to_end(&mut array);
+		self.file.seek(SeekFrom::Start(self.len))?;
+		result?;
+
+		Ok( array )
+	}
+}
+
+///The body of the first record, `None` - no more records.
+fn next_record(array: &[u8]) -> Option<Result<&[u8], cluLamanshErr>> {
+	if array.is_empty() {
+		return None;
+	}
+	let len = match array.get(.. 4) {
+		Some(a) => BigEndian::read_u32(a) as usize,
+		_ => return Some( Err( cluLamanshErr::ErrGetSizeArray ) ),
+	};
+	let (record, trailer) = match (array.get(.. 4 + len), array.get(4 + len .. 4 + len + 4)) {
+		(Some(a), Some(b)) => (a, b),
+		_ => return Some( Err( cluLamanshErr::ErrGetValue ) ),
+	};
+
+	let mut crc = Crc32c::new();
+	crc.update(record);
+
+	let expected = BigEndian::read_u32(trailer);
+	let actual = crc.finish();
+	if expected != actual {
+		return Some( Err( cluLamanshErr::ChecksumMismatch { expected: expected, actual: actual } ) );
+	}
+
+	Some( Ok( &record[4 ..] ) )
+}
+
+
+///Records of the log as handlers, stops after the first damaged record.
+#[derive(Debug, Clone)]
+pub struct LamanshLogIter<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	array: &'a [u8],
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshLogIter<'a, NC, N> {
+	#[inline]
+	pub fn new(array: &'a [u8]) -> Self {
+		Self {
+			array: array,
+
+			_n_count_phantom: PhantomData,
+			_n_phantom: PhantomData,
+		}
+	}
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> Iterator for LamanshLogIter<'a, NC, N> {
+	type Item = Result<cluLamansh<'a, NC, N>, cluLamanshErr>;
+
+	fn next(&mut self) -> Option< Self::Item > {
+		match next_record(self.array)? {
+			Ok(a) => {
+				self.array = &self.array[a.len() + LAMANSH_LOG_FRAME_LEN ..];
+				Some( cluLamansh::new(a) )
+			},
+			Err(e) => {
+				self.array = &[];
+				Some( Err( e ) )
+			},
+		}
+	}
+}
@@ -7,6 +7,30 @@ pub mod build;
 pub mod sized;
 pub mod array;
 pub mod buffer;
+pub mod record;
+pub mod schema;
+pub mod envelope;
+pub mod checksum;
+pub mod mutable;
+pub mod editor;
+pub mod concat;
+pub mod transcode;
+pub mod log;
+pub mod stream;
+pub mod file;
+pub mod indexed;
+pub mod range;
+pub mod dump;
+pub mod json;
+pub mod codec;
+pub mod auth;
+pub mod dedup;
+pub mod delta;
+pub mod offsets;
+pub mod scratch;
+pub mod pool;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 
 use lamansh::iter::cluLamanshIntoIter;
 use std::convert::TryFrom;
@@ -14,7 +38,7 @@ use std::marker::PhantomData;
 use self::sized::LamanshSized;
 use lamansh::iter::cluLamanshIter;
 
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[allow(non_camel_case_types)]
 pub struct cluLamansh<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
 	array: &'a [u8],
@@ -34,32 +58,15 @@ impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, N
 	}
 	
 	pub fn array(array: &'a [u8]) -> Result< Self, cluLamanshErr > {
-		
-		let ( value_head_array, value_array ) = {			
-			let byted_count = NC::byted();
-
-			let count_header = {
-				match array.get( .. byted_count) {
-					Some(a) => NC::read_usize(a)+1,
-					_ => return Err( cluLamanshErr::ErrGetSizeArray ),
-				}
-			};
-			
-
-			let size_value = N::byted();
-			let n = byted_count + (size_value * count_header);
-
-			let header_array = match array.get(byted_count .. n) {
-				Some(a) => a,
-				_ => return Err( cluLamanshErr::ErrGetValueHead ),
-			};
+		let ( value_head_array, value_array ) = {
+			let n = header_len::<NC, N>(array)?;
 
 			let value_array = match array.get(n .. ) {
 				Some(a) => a,
 				_ => return Err( cluLamanshErr::ErrGetValue ),
 			};
 			
-			( header_array, value_array )
+			( &array[NC::byted() .. n], value_array )
 		};
 		
 		
@@ -77,6 +84,12 @@ impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, N
 		 )
 	}
 	
+	///Create a handler after checking the checksum trailer (`ToLamansh::to_lamansh_checksum`).
+	#[inline]
+	pub fn new_checksum(array: &'a [u8]) -> Result< Self, cluLamanshErr > {
+		Self::array( checksum::split_checksum(array)? )
+	}
+	
 	#[inline]
 	pub fn iter<'b>(&'a self) -> cluLamanshIter<'a, 'b, NC, N> {
 		cluLamanshIter::new(self)
@@ -85,6 +98,51 @@ impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, N
 	pub fn into_iter(self) -> cluLamanshIntoIter<'a, NC, N> {
 		cluLamanshIntoIter::new(self)
 	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.value_head_array.len() / N::byted()
+	}
+
+	///Len of the values according to the headers, `None` - the headers go beyond the values
+	pub(crate) fn values_len(&self) -> Option<usize> {
+		let mut len_n = 0usize;
+		for a in self.value_head_array.chunks(N::byted()) {
+			len_n = len_n.checked_add(N::read_usize(a))?;
+		}
+		if len_n > self.value_array.len() {
+			return None;
+		}
+		Some( len_n )
+	}
+}
+
+///Len of the count header and the value headers, the values start after it.
+pub(crate) fn header_len<NC: LamanshSized + 'static, N: LamanshSized + 'static>(array: &[u8]) -> Result< usize, cluLamanshErr > {
+	let byted_count = NC::byted();
+
+	let count_header = {
+		match array.get( .. byted_count) {
+			Some(a) => NC::read_usize(a)+1,
+			_ => return Err( cluLamanshErr::ErrGetSizeArray ),
+		}
+	};
+	
+
+	let size_value = N::byted();
+	let n = byted_count + (size_value * count_header);
+
+	if array.len() < n {
+		return Err( cluLamanshErr::ErrGetValueHead );
+	}
+
+	Ok( n )
+}
+
+///Error of the protocol as `io::ErrorKind::InvalidData`
+pub(crate) fn invalid_data(e: cluLamanshErr) -> ::std::io::Error {
+	::std::io::Error::new(::std::io::ErrorKind::InvalidData, format!("{:?}", e))
 }
 
 
@@ -104,6 +162,60 @@ pub enum cluLamanshErr {
 
 	///Going beyond the value
 	ErrGetNextValue,
+
+	///The number of elements is less than the record requires
+	ErrRecordLen,
+
+	///The element cannot be converted to the type of the record field
+	ErrFieldValue,
+
+	///It is not possible to get the envelope
+	ErrGetEnvelope,
+
+	///The envelope does not start with the magic bytes
+	ErrEnvelopeMagic,
+
+	///Unsupported version of the envelope
+	ErrEnvelopeVersion,
+
+	///Unknown profile or the profile does not match
+	ErrEnvelopeProfile,
+
+	///Unknown flags
+	ErrEnvelopeFlags,
+
+	///It is not possible to get the checksum trailer
+	ErrGetChecksum,
+
+	///The element with the index is missing
+	ErrGetIndex,
+
+	///The len of the new value does not match the element
+	ErrOverwriteLen,
+
+	///Unknown codec or the encoded value is damaged
+	ErrCodec,
+
+	///It is not possible to get the authentication tag
+	ErrGetTag,
+
+	///The authentication tag does not match the data
+	ErrAuthTag,
+
+	///The reference does not point to an earlier element
+	ErrDedupRef,
+
+	///The patch was made against another base message
+	ErrDeltaBase,
+
+	///The patch is damaged
+	ErrDeltaPatch,
+
+	///The checksum trailer does not match the data
+	ChecksumMismatch {
+		expected: u32,
+		actual: u32,
+	},
 }
 
 
@@ -0,0 +1,128 @@
+/*! Changing the values of the protocol in place, the lengths of the elements are not changed. */
+
+use std::marker::PhantomData;
+use dyn_chunks::MutDynExactChunks;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamanshErr;
+use lamansh::header_len;
+
+#[derive(Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshMut<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	value_head_array: &'a [u8],
+	value_array: &'a mut [u8],
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshMut<'a, NC, N> {
+	///Create a handler, all elements must be within the values.
+	pub fn new(array: &'a mut [u8]) -> Result< Self, cluLamanshErr > {
+		let n = header_len::<NC, N>(array)?;
+		let (head_array, value_array) = array.split_at_mut(n);
+		let head_array: &'a [u8] = head_array;
+		let value_head_array = &head_array[NC::byted() .. ];
+
+		let mut len_n = 0usize;
+		for a in value_head_array.chunks(N::byted()) {
+			len_n = match len_n.checked_add(N::read_usize(a)) {
+				Some(a) => a,
+				_ => return Err( cluLamanshErr::ErrGetNextValue ),
+			};
+		}
+		if len_n > value_array.len() {
+			return Err( cluLamanshErr::ErrGetNextValue );
+		}
+
+		Ok(
+			Self {
+				value_head_array: value_head_array,
+				value_array: value_array,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.value_head_array.len() / N::byted()
+	}
+
+	#[inline]
+	fn value_len(&self, index: usize) -> usize {
+		let n = index * N::byted();
+		N::read_usize(&self.value_head_array[n .. n + N::byted()])
+	}
+
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+		if index >= self.len() {
+			return None;
+		}
+
+		let start = (0 .. index).map(|a| self.value_len(a)).sum::<usize>();
+		let end = start + self.value_len(index);
+
+		Some( &mut self.value_array[start .. end] )
+	}
+
+	///Replace the value of the element, the len must match.
+	pub fn overwrite(&mut self, index: usize, array: &[u8]) -> Result< (), cluLamanshErr > {
+		match self.get_mut(index) {
+			Some(ref mut a) if a.len() == array.len() => {
+				a.copy_from_slice(array);
+				Ok( () )
+			},
+			Some(_) => Err( cluLamanshErr::ErrOverwriteLen ),
+			None => Err( cluLamanshErr::ErrGetIndex ),
+		}
+	}
+
+	#[inline]
+	pub fn iter_mut<'b>(&'b mut self) -> cluLamanshIterMut<'b, N> {
+		cluLamanshIterMut {
+			value_head_array: self.value_head_array,
+			value_array: MutDynExactChunks::array(self.value_array),
+
+			header_n: 0,
+
+			_n_phantom: PhantomData,
+		}
+	}
+}
+
+
+#[derive(Debug)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshIterMut<'b, N: LamanshSized + 'static> {
+	value_head_array: &'b [u8],
+	value_array: MutDynExactChunks<'b, u8>,
+
+	header_n: usize,
+
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'b, N: LamanshSized + 'static> Iterator for cluLamanshIterMut<'b, N> {
+	type Item = &'b mut [u8];
+
+	#[inline]
+	fn next(&mut self) -> Option< Self::Item > {
+		let size_len = {
+			let new_n = N::byted() + self.header_n;
+			match self.value_head_array.get(self.header_n .. new_n) {
+				Some(a) => {
+					self.header_n = new_n;
+
+					N::read_usize(a)
+				},
+				_ => return None,
+			}
+		};
+
+		self.value_array.next_usize(size_len)
+	}
+}
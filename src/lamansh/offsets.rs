@@ -0,0 +1,103 @@
+/*!
+Offset table of the protocol: all the headers are decoded in one pass,
+then every element is found without reading the headers.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::new_custom_lamansh;
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::sized::U16;
+use cluLamansh::lamansh::sized::U32;
+
+pub fn main() {
+	let values = (0 .. 1000u32).map(|a| a.to_string().into_bytes()).collect::<Vec<_>>();
+	let array = (&values[..]).to_lamansh::<U16, U32>().unwrap();
+
+	let lamansh = new_custom_lamansh::<U16, U32>(&array).unwrap();
+	let offsets = lamansh.offsets().unwrap();
+	assert_eq!(offsets.get(999), Some(&b"999"[..]));
+	assert_eq!(offsets.iter().count(), 1000);
+}
+```
+ */
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, NC, N> {
+	///Offset table of the values (`len + 1` positions relative to the start of the values). Using an external table.
+	pub fn offsets_into(&self, table: &mut Vec<usize>) -> Result<(), cluLamanshErr> {
+		table.clear();
+		table.push(0);
+
+		match N::read_ends(self.value_head_array, table) {
+			Some(a) if a <= self.value_array.len() => Ok( () ),
+			_ => Err( cluLamanshErr::ErrGetNextValue ),
+		}
+	}
+
+	///Offset table of the values, the headers must not go beyond the values.
+	pub fn offsets(&self) -> Result<cluLamanshOffsets<'a, NC, N>, cluLamanshErr> {
+		let mut table = Vec::with_capacity(self.len() + 1);
+		self.offsets_into(&mut table)?;
+
+		Ok(
+			cluLamanshOffsets {
+				value_array: self.value_array,
+				table: table,
+
+				_n_count_phantom: PhantomData,
+				_n_phantom: PhantomData,
+			}
+		)
+	}
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshOffsets<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	value_array: &'a [u8],
+	//start of every value and the end of the last one
+	table: Vec<usize>,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshOffsets<'a, NC, N> {
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.table.len() - 1
+	}
+
+	///Position of the value of the element relative to the start of the values
+	#[inline]
+	pub fn value_range(&self, index: usize) -> Option<Range<usize>> {
+		match index < self.len() {
+			true => Some( self.table[index] .. self.table[index + 1] ),
+			false => None,
+		}
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+		let array: &'a [u8] = self.value_array;
+		self.value_range(index).map(|a| &array[a])
+	}
+
+	#[inline]
+	pub fn iter<'b>(&'b self) -> impl Iterator<Item = &'a [u8]> + 'b {
+		let array: &'a [u8] = self.value_array;
+		self.table.windows(2).map(move |a| &array[a[0] .. a[1]])
+	}
+
+	#[inline]
+	pub fn as_table(&self) -> &[usize] {
+		&self.table
+	}
+}
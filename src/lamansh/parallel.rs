@@ -0,0 +1,92 @@
+/*!
+Parallel building of the protocol (feature `parallel`) for large arrays of values.
+
+The positions of the values are calculated first, the buffer is split into disjoint regions
+and the headers and the values are copied concurrently. The result is the same as `ToLamansh::update_buffer`.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::parallel::to_lamansh_parallel;
+use cluLamansh::lamansh::sized::U16;
+use cluLamansh::lamansh::sized::U32;
+
+pub fn main() {
+	let values = (0 .. 1000u32).map(|a| vec![a as u8; 4096]).collect::<Vec<_>>();
+
+	let array = to_lamansh_parallel::<U16, U32, _>(&values).unwrap();
+	assert_eq!(array, (&values[..]).to_lamansh::<U16, U32>().unwrap());
+}
+```
+ */
+
+extern crate rayon;
+use self::rayon::prelude::*;
+
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+
+///Elements copied by one task
+const LAMANSH_PARALLEL_MIN_LEN: usize = 64;
+
+///Build the protocol from the values in parallel.
+pub fn to_lamansh_parallel<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]> + Sync>(array: &[A]) -> Result<Vec<u8>, ToLamanshErr> {
+	let mut buffer = LamanshBuffer::new();
+	update_buffer_parallel::<NC, N, A>(array, &mut buffer)?;
+
+	Ok( buffer.into() )
+}
+
+///Build the protocol from the values in parallel. Using an external buffer.
+pub fn update_buffer_parallel<NC: LamanshSized + 'static, N: LamanshSized + 'static, A: AsRef<[u8]> + Sync>(array: &[A], vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+	let len_elements = array.len();
+	if len_elements == 0 {
+		return Err( ToLamanshErr::EmptyArray );
+	}
+	if len_elements > NC::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+
+	let mut len_n = 0usize;
+	for a in array.iter() {
+		let len = a.as_ref().len();
+		if len > N::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+		len_n = match len_n.checked_add(len) {
+			Some(a) => a,
+			_ => return Err( ToLamanshErr::ValueOverflow ),
+		};
+	}
+
+	let n_c_sized = NC::byted();
+	let n_sized = N::byted();
+	vec_result.set_len(n_c_sized + (n_sized * len_elements) + len_n);
+
+	let (count_array, value_head_array) = vec_result.split_at_mut(n_c_sized);
+	let (value_head_array, value_array) = value_head_array.split_at_mut(n_sized * len_elements);
+	NC::write_usize(len_elements-1, count_array);
+
+	//disjoint region of every value
+	let mut regions = Vec::with_capacity(len_elements);
+	let mut value_array = value_array;
+	for a in array.iter() {
+		let (region, next) = {value_array}.split_at_mut(a.as_ref().len());
+		regions.push(region);
+		value_array = next;
+	}
+
+	value_head_array.par_chunks_mut(n_sized)
+		.zip(regions.into_par_iter())
+		.zip(array.par_iter())
+		.with_min_len(LAMANSH_PARALLEL_MIN_LEN)
+		.for_each(|((head, region), a)| {
+			let a = a.as_ref();
+			N::write_usize(a.len(), head);
+			region.copy_from_slice(a);
+		});
+
+	Ok( () )
+}
@@ -0,0 +1,182 @@
+/*!
+Thread-safe pool of `LamanshBuffer`: the buffer is taken with `get` and returned to the pool when the guard is dropped.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::pool::LamanshBufferPool;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let pool = LamanshBufferPool::new().with_max_len(8);
+
+	for _ in 0 .. 3 {
+		let mut buffer = pool.get();
+		[&b"TEST"[..]].update_buffer::<U8, U16>(&mut buffer).unwrap();
+		assert_eq!(&buffer[..], &[0, 0, 4, b'T', b'E', b'S', b'T']);
+	}
+
+	let stats = pool.stats();
+	assert_eq!((stats.hits, stats.misses), (2, 1));
+}
+```
+ */
+
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use lamansh::buffer::LamanshBuffer;
+
+///Buffers kept by default
+pub const LAMANSH_POOL_MAX_LEN: usize = 64;
+///Capacity of the returned buffers by default, larger buffers are shrunk
+pub const LAMANSH_POOL_SHRINK_CAPACITY: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct LamanshBufferPool {
+	buffers: Mutex<Vec<LamanshBuffer>>,
+	max_len: usize,
+	shrink_capacity: usize,
+
+	hits: AtomicUsize,
+	misses: AtomicUsize,
+}
+
+///Use of the pool
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LamanshPoolStats {
+	///`get` returned a buffer of the pool
+	pub hits: usize,
+	///`get` created a new buffer
+	pub misses: usize,
+	///Buffers in the pool now
+	pub pooled: usize,
+}
+
+impl LamanshBufferPool {
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			buffers: Mutex::new(Vec::new()),
+			max_len: LAMANSH_POOL_MAX_LEN,
+			shrink_capacity: LAMANSH_POOL_SHRINK_CAPACITY,
+
+			hits: AtomicUsize::new(0),
+			misses: AtomicUsize::new(0),
+		}
+	}
+
+	///Buffers kept by the pool, the other returned buffers are dropped
+	#[inline]
+	pub fn with_max_len(mut self, max_len: usize) -> Self {
+		self.max_len = max_len;
+		self
+	}
+
+	///Capacity of the returned buffer after which it is shrunk
+	#[inline]
+	pub fn with_shrink_capacity(mut self, capacity: usize) -> Self {
+		self.shrink_capacity = capacity;
+		self
+	}
+
+	//The buffers stay valid if the thread panicked with the lock
+	#[inline]
+	fn lock(&self) -> MutexGuard<Vec<LamanshBuffer>> {
+		match self.buffers.lock() {
+			Ok(a) => a,
+			Err(e) => e.into_inner(),
+		}
+	}
+
+	///Empty buffer of the pool or a new one
+	pub fn get(&self) -> LamanshPooledBuffer {
+		let buffer = match self.lock().pop() {
+			Some(a) => {
+				self.hits.fetch_add(1, Ordering::Relaxed);
+				a
+			},
+			None => {
+				self.misses.fetch_add(1, Ordering::Relaxed);
+				LamanshBuffer::new()
+			},
+		};
+
+		LamanshPooledBuffer {
+			pool: self,
+			buffer: Some(buffer),
+		}
+	}
+
+	fn put(&self, mut buffer: LamanshBuffer) {
+		buffer.clear();
+		if buffer.capacity() > self.shrink_capacity {
+			buffer.shrink_to(self.shrink_capacity);
+		}
+
+		let mut buffers = self.lock();
+		if buffers.len() < self.max_len {
+			buffers.push(buffer);
+		}
+	}
+
+	pub fn stats(&self) -> LamanshPoolStats {
+		LamanshPoolStats {
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+			pooled: self.lock().len(),
+		}
+	}
+}
+
+impl Default for LamanshBufferPool {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+///Buffer of the pool, returned to the pool on drop
+#[derive(Debug)]
+pub struct LamanshPooledBuffer<'p> {
+	pool: &'p LamanshBufferPool,
+	//`None` only after `into_inner`
+	buffer: Option<LamanshBuffer>,
+}
+
+impl<'p> LamanshPooledBuffer<'p> {
+	///Take the buffer out of the pool
+	#[inline]
+	pub fn into_inner(mut self) -> LamanshBuffer {
+		self.buffer.take().unwrap()
+	}
+}
+
+impl<'p> Deref for LamanshPooledBuffer<'p> {
+	type Target = LamanshBuffer;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.buffer.as_ref().unwrap()
+	}
+}
+
+impl<'p> DerefMut for LamanshPooledBuffer<'p> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.buffer.as_mut().unwrap()
+	}
+}
+
+impl<'p> Drop for LamanshPooledBuffer<'p> {
+	fn drop(&mut self) {
+		if let Some(a) = self.buffer.take() {
+			self.pool.put(a);
+		}
+	}
+}
@@ -0,0 +1,117 @@
+/*! Views of a part of the elements of the protocol without copying. */
+
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use lamansh::build::ToLamansh;
+use lamansh::build::ToLamanshErr;
+use lamansh::iter::cluLamanshIntoIter;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Elements of the protocol in the range.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshRange<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	//headers and values of the range only
+	lamansh: cluLamansh<'a, NC, N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshRange<'a, NC, N> {
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.lamansh.len()
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	#[inline]
+	pub fn iter(&self) -> cluLamanshIntoIter<'a, NC, N> {
+		self.lamansh.clone().into_iter()
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<Result<&'a [u8], cluLamanshErr>> {
+		self.iter().nth(index)
+	}
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamansh<'a, NC, N> {
+	///Elements in the range, the range must be within the elements.
+	pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Result<cluLamanshRange<'a, NC, N>, cluLamanshErr> {
+		let len = self.len();
+		let start = match range.start_bound() {
+			Bound::Included(a) => Some( *a ),
+			Bound::Excluded(a) => a.checked_add(1),
+			Bound::Unbounded => Some( 0 ),
+		};
+		let end = match range.end_bound() {
+			Bound::Included(a) => a.checked_add(1),
+			Bound::Excluded(a) => Some( *a ),
+			Bound::Unbounded => Some( len ),
+		};
+		let (start, end) = match (start, end) {
+			(Some(start), Some(end)) if start <= end && end <= len => (start, end),
+			_ => return Err( cluLamanshErr::ErrGetIndex ),
+		};
+
+		let n_sized = N::byted();
+		let value_head_array: &'a [u8] = self.value_head_array;
+		let mut value_n = 0usize;
+		for a in value_head_array[.. start * n_sized].chunks(n_sized) {
+			value_n = match value_n.checked_add(N::read_usize(a)) {
+				Some(a) => a,
+				_ => return Err( cluLamanshErr::ErrGetNextValue ),
+			};
+		}
+		let value_array: &'a [u8] = match self.value_array.get(value_n ..) {
+			Some(a) => a,
+			_ => return Err( cluLamanshErr::ErrGetNextValue ),
+		};
+
+		Ok(
+			cluLamanshRange {
+				lamansh: cluLamansh {
+					array: self.array,
+
+					value_head_array: &value_head_array[start * n_sized .. end * n_sized],
+					value_array: value_array,
+
+					_n_count_phantom: self._n_count_phantom,
+					_n_phantom: self._n_phantom,
+				},
+			}
+		)
+	}
+
+	///Elements before the index and starting from it.
+	#[inline]
+	pub fn split_at(&self, index: usize) -> Result<(cluLamanshRange<'a, NC, N>, cluLamanshRange<'a, NC, N>), cluLamanshErr> {
+		Ok( (self.slice(.. index)?, self.slice(index ..)?) )
+	}
+
+	///New protocol from the elements with the indexes, in the order of the indexes.
+	pub fn select(&self, index: &[usize]) -> Result<Vec<u8>, ToLamanshErr> {
+		let mut values = Vec::with_capacity(self.len());
+		for a in self.clone().into_iter() {
+			match a {
+				Ok(a) => values.push(a),
+				Err(_) => return Err( ToLamanshErr::InvalidLamansh ),
+			}
+		}
+
+		let mut array = Vec::with_capacity(index.len());
+		for a in index.iter() {
+			match values.get(*a) {
+				Some(a) => array.push(*a),
+				_ => return Err( ToLamanshErr::IndexOverflow ),
+			}
+		}
+
+		array.to_lamansh::<NC, N>()
+	}
+}
@@ -0,0 +1,201 @@
+/*!
+Records: structures whose fields are stored as positional Lamansh elements.
+
+Implementations are usually generated by `#[derive(ToLamansh, FromLamansh)]` from the `cluLamansh_derive` crate.
+ */
+
+extern crate byteorder;
+use self::byteorder::BigEndian;
+use self::byteorder::ByteOrder;
+
+use std::borrow::Cow;
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Writing the structure to the protocol, one field per element.
+pub trait ToLamanshRecord {
+	type CountSized: LamanshSized + 'static;
+	type ValueSized: LamanshSized + 'static;
+
+	///Safe method of creating binary data. Using an external buffer.
+	fn update_buffer_record(&self, vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr>;
+
+	///Safe method of creating binary data.
+	fn to_lamansh_record(&self) -> Result<Vec<u8>, ToLamanshErr> {
+		let mut buffer = LamanshBuffer::new();
+		self.update_buffer_record(&mut buffer)?;
+
+		Ok( buffer.into() )
+	}
+}
+
+///Reading the structure from the protocol, one field per element. Elements after the last field are ignored.
+pub trait FromLamanshRecord<'a>: Sized {
+	type CountSized: LamanshSized + 'static;
+	type ValueSized: LamanshSized + 'static;
+
+	fn from_lamansh(lamansh: &cluLamansh<'a, Self::CountSized, Self::ValueSized>) -> Result<Self, cluLamanshErr>;
+
+	///Create a handler from the received data and read the structure.
+	#[inline]
+	fn from_lamansh_array(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		let lamansh = cluLamansh::new(array)?;
+		Self::from_lamansh(&lamansh)
+	}
+}
+
+
+///Type of the record field that can be written to the element.
+pub trait ToLamanshField {
+	fn to_lamansh_field(&self) -> Cow<[u8]>;
+}
+
+///Type of the record field that can be read from the element.
+pub trait FromLamanshField<'a>: Sized {
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr>;
+}
+
+
+impl<'a> ToLamanshField for &'a [u8] {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Borrowed(self)
+	}
+}
+impl<'a> FromLamanshField<'a> for &'a [u8] {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		Ok( array )
+	}
+}
+
+impl ToLamanshField for Vec<u8> {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Borrowed(self)
+	}
+}
+impl<'a> FromLamanshField<'a> for Vec<u8> {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		Ok( array.to_vec() )
+	}
+}
+
+impl<'a> ToLamanshField for &'a str {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Borrowed(self.as_bytes())
+	}
+}
+impl<'a> FromLamanshField<'a> for &'a str {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		match ::std::str::from_utf8(array) {
+			Ok(a) => Ok( a ),
+			_ => Err( cluLamanshErr::ErrFieldValue ),
+		}
+	}
+}
+
+impl ToLamanshField for String {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Borrowed(self.as_bytes())
+	}
+}
+impl<'a> FromLamanshField<'a> for String {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		let a: &str = FromLamanshField::from_lamansh_field(array)?;
+		Ok( a.to_string() )
+	}
+}
+
+impl ToLamanshField for bool {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Owned(vec![*self as u8])
+	}
+}
+impl<'a> FromLamanshField<'a> for bool {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		match array {
+			[0] => Ok( false ),
+			[1] => Ok( true ),
+			_ => Err( cluLamanshErr::ErrFieldValue ),
+		}
+	}
+}
+
+
+macro_rules! build_field_num {
+	($($t:ty, $size:expr, $read:ident, $write:ident;)+) => {
+		$(
+			///Big Endian, as well as the headers of the protocol.
+			impl ToLamanshField for $t {
+				#[inline]
+				fn to_lamansh_field(&self) -> Cow<[u8]> {
+					let mut array = vec![0u8; $size];
+					BigEndian::$write(&mut array, *self);
+
+					Cow::Owned(array)
+				}
+			}
+			impl<'a> FromLamanshField<'a> for $t {
+				#[inline]
+				fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+					if array.len() != $size {
+						return Err( cluLamanshErr::ErrFieldValue );
+					}
+					Ok( BigEndian::$read(array) )
+				}
+			}
+		)+
+	};
+}
+
+build_field_num! {
+	u16, 2, read_u16, write_u16;
+	u32, 4, read_u32, write_u32;
+	u64, 8, read_u64, write_u64;
+	i16, 2, read_i16, write_i16;
+	i32, 4, read_i32, write_i32;
+	i64, 8, read_i64, write_i64;
+}
+
+impl ToLamanshField for u8 {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Owned(vec![*self])
+	}
+}
+impl<'a> FromLamanshField<'a> for u8 {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		match array {
+			[a] => Ok( *a ),
+			_ => Err( cluLamanshErr::ErrFieldValue ),
+		}
+	}
+}
+
+impl ToLamanshField for i8 {
+	#[inline]
+	fn to_lamansh_field(&self) -> Cow<[u8]> {
+		Cow::Owned(vec![*self as u8])
+	}
+}
+impl<'a> FromLamanshField<'a> for i8 {
+	#[inline]
+	fn from_lamansh_field(array: &'a [u8]) -> Result<Self, cluLamanshErr> {
+		match array {
+			[a] => Ok( *a as i8 ),
+			_ => Err( cluLamanshErr::ErrFieldValue ),
+		}
+	}
+}
@@ -0,0 +1,196 @@
+/*!
+Description of the expected elements of the protocol and its validation.
+ */
+
+use std::str;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+
+///Expected elements of the protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LamanshSchema {
+	count: LamanshSchemaCount,
+
+	elements: Vec<Vec<LamanshConstraint>>,
+}
+
+///Expected number of elements.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshSchemaCount {
+	Any,
+	Exact(usize),
+	///Min and max, inclusive
+	Range(usize, usize),
+}
+
+///Constraint of one element.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshConstraint {
+	FixedLen(usize),
+	MaxLen(usize),
+	Utf8,
+	Type(LamanshValueType),
+}
+
+///Value types of the elements, encoded as the fields of `lamansh::record`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshValueType {
+	Bytes,
+	Str,
+	Bool,
+	U8,
+	U16,
+	U32,
+	U64,
+	I8,
+	I16,
+	I32,
+	I64,
+}
+
+impl LamanshValueType {
+	///Whether the element can be read as this type
+	pub fn is_valid(&self, array: &[u8]) -> bool {
+		match *self {
+			LamanshValueType::Bytes => true,
+			LamanshValueType::Str => str::from_utf8(array).is_ok(),
+			LamanshValueType::Bool => array == [0] || array == [1],
+			LamanshValueType::U8 | LamanshValueType::I8 => array.len() == 1,
+			LamanshValueType::U16 | LamanshValueType::I16 => array.len() == 2,
+			LamanshValueType::U32 | LamanshValueType::I32 => array.len() == 4,
+			LamanshValueType::U64 | LamanshValueType::I64 => array.len() == 8,
+		}
+	}
+}
+
+///Violation of the schema, `index` - `None` for the whole protocol.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LamanshViolation {
+	pub index: Option<usize>,
+	pub kind: LamanshViolationKind,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshViolationKind {
+	///The number of elements does not match `LamanshSchemaCount`
+	Count(usize),
+	///Expected len, actual len
+	FixedLen(usize, usize),
+	///Max len, actual len
+	MaxLen(usize, usize),
+	Utf8,
+	Type(LamanshValueType),
+
+	///The element could not be read, the following elements are not checked
+	Lamansh(cluLamanshErr),
+}
+
+
+impl LamanshSchema {
+	///Any number of elements
+	#[inline]
+	pub fn new() -> Self {
+		Self::count(LamanshSchemaCount::Any)
+	}
+	#[inline]
+	pub fn exact(count: usize) -> Self {
+		Self::count(LamanshSchemaCount::Exact(count))
+	}
+	#[inline]
+	pub fn range(min: usize, max: usize) -> Self {
+		Self::count(LamanshSchemaCount::Range(min, max))
+	}
+	#[inline]
+	pub fn count(count: LamanshSchemaCount) -> Self {
+		Self {
+			count: count,
+
+			elements: Vec::new(),
+		}
+	}
+
+	///Add a constraint to the element
+	pub fn push(&mut self, index: usize, constraint: LamanshConstraint) {
+		if self.elements.len() <= index {
+			self.elements.resize(index+1, Vec::new());
+		}
+		self.elements[index].push(constraint);
+	}
+
+	///Add a constraint to the element
+	#[inline]
+	pub fn with(mut self, index: usize, constraint: LamanshConstraint) -> Self {
+		self.push(index, constraint);
+		self
+	}
+
+	#[inline]
+	pub fn constraints(&self, index: usize) -> &[LamanshConstraint] {
+		match self.elements.get(index) {
+			Some(a) => a,
+			_ => &[],
+		}
+	}
+
+	///Check the received protocol, all violations are returned
+	pub fn validate<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(&self, lamansh: &cluLamansh<'a, NC, N>) -> Result<(), Vec<LamanshViolation>> {
+		let mut result = Vec::new();
+		let mut count = 0;
+		for (index, value) in lamansh.clone().into_iter().enumerate() {
+			match value {
+				Ok(a) => self.check_element(index, a, &mut result),
+				Err(e) => {
+					result.push(LamanshViolation { index: Some(index), kind: LamanshViolationKind::Lamansh(e) });
+					return Err( result );
+				},
+			}
+			count += 1;
+		}
+		self.check_count(count, &mut result);
+
+		match result.len() {
+			0 => Ok( () ),
+			_ => Err( result ),
+		}
+	}
+
+	///Check the array before creating the protocol, all violations are returned
+	pub fn validate_array<A: AsRef<[u8]>>(&self, array: &[A]) -> Result<(), Vec<LamanshViolation>> {
+		let mut result = Vec::new();
+		for (index, value) in array.iter().enumerate() {
+			self.check_element(index, value.as_ref(), &mut result);
+		}
+		self.check_count(array.len(), &mut result);
+
+		match result.len() {
+			0 => Ok( () ),
+			_ => Err( result ),
+		}
+	}
+
+	fn check_count(&self, count: usize, result: &mut Vec<LamanshViolation>) {
+		let is_valid = match self.count {
+			LamanshSchemaCount::Any => true,
+			LamanshSchemaCount::Exact(a) => count == a,
+			LamanshSchemaCount::Range(min, max) => count >= min && count <= max,
+		};
+		if !is_valid {
+			result.push(LamanshViolation { index: None, kind: LamanshViolationKind::Count(count) });
+		}
+	}
+
+	fn check_element(&self, index: usize, array: &[u8], result: &mut Vec<LamanshViolation>) {
+		let len = array.len();
+		for constraint in self.constraints(index) {
+			let kind = match *constraint {
+				LamanshConstraint::FixedLen(a) if len != a => LamanshViolationKind::FixedLen(a, len),
+				LamanshConstraint::MaxLen(a) if len > a => LamanshViolationKind::MaxLen(a, len),
+				LamanshConstraint::Utf8 if str::from_utf8(array).is_err() => LamanshViolationKind::Utf8,
+				LamanshConstraint::Type(a) if !a.is_valid(array) => LamanshViolationKind::Type(a),
+				_ => continue,
+			};
+			result.push(LamanshViolation { index: Some(index), kind: kind });
+		}
+	}
+}
@@ -0,0 +1,132 @@
+/*!
+Eliminating the re-creation of the offset tables when decoding a stream of messages,
+the counterpart of `LamanshBuffer`.
+
+```
+extern crate cluLamansh;
+
+use cluLamansh::lamansh::build::ToLamansh;
+use cluLamansh::lamansh::scratch::LamanshDecodeScratch;
+use cluLamansh::lamansh::scratch::parse_into;
+use cluLamansh::lamansh::sized::U8;
+use cluLamansh::lamansh::sized::U16;
+
+pub fn main() {
+	let mut scratch = LamanshDecodeScratch::new();
+
+	for a in ["first", "second"].iter() {
+		let array = [a.as_bytes(), &b"end"[..]].to_lamansh::<U8, U16>().unwrap();
+
+		let view = parse_into::<U8, U16>(&mut scratch, &array).unwrap();
+		assert_eq!(view.get(0), Some(a.as_bytes()));
+		assert_eq!(view.get(1), Some(&b"end"[..]));
+	}
+}
+```
+ */
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamanshErr;
+use lamansh::header_len;
+
+///Reusable positions of the values of the decoded message.
+#[derive(Debug, Default, Clone)]
+pub struct LamanshDecodeScratch {
+	///Start of the values and the end of every value
+	ends: Vec<usize>,
+}
+
+impl LamanshDecodeScratch {
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			ends: Vec::new(),
+		}
+	}
+
+	#[inline]
+	pub fn with_capacity(len: usize) -> Self {
+		Self {
+			ends: Vec::with_capacity(len + 1),
+		}
+	}
+
+	///Number of elements that fit without reallocation
+	#[inline]
+	pub fn capacity(&self) -> usize {
+		self.ends.capacity().saturating_sub(1)
+	}
+
+	#[inline]
+	pub fn parse<'a, 's, NC: LamanshSized + 'static, N: LamanshSized + 'static>(&'s mut self, array: &'a [u8]) -> Result<cluLamanshView<'a, 's, NC, N>, cluLamanshErr> {
+		parse_into(self, array)
+	}
+}
+
+///Decode the headers of the message into the scratch, the view indexes the values in O(1).
+pub fn parse_into<'a, 's, NC: LamanshSized + 'static, N: LamanshSized + 'static>(scratch: &'s mut LamanshDecodeScratch, array: &'a [u8]) -> Result<cluLamanshView<'a, 's, NC, N>, cluLamanshErr> {
+	let n = header_len::<NC, N>(array)?;
+	let value_head_array = &array[NC::byted() .. n];
+	let value_array = &array[n ..];
+
+	let ends = &mut scratch.ends;
+	ends.clear();
+	ends.push(0);
+
+	match N::read_ends(value_head_array, ends) {
+		Some(a) if a <= value_array.len() => {},
+		_ => return Err( cluLamanshErr::ErrGetNextValue ),
+	}
+
+	Ok(
+		cluLamanshView {
+			value_array: value_array,
+			ends: ends,
+
+			_n_count_phantom: PhantomData,
+			_n_phantom: PhantomData,
+		}
+	)
+}
+
+///Message decoded into `LamanshDecodeScratch`
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct cluLamanshView<'a, 's, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	value_array: &'a [u8],
+	ends: &'s [usize],
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, 's, NC: LamanshSized + 'static, N: LamanshSized + 'static> cluLamanshView<'a, 's, NC, N> {
+	///Number of elements
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.ends.len() - 1
+	}
+
+	///Position of the value of the element relative to the start of the values
+	#[inline]
+	pub fn value_range(&self, index: usize) -> Option<Range<usize>> {
+		match index.checked_add(1).and_then(|a| self.ends.get(a)) {
+			Some(end) => Some( self.ends[index] .. *end ),
+			None => None,
+		}
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+		let array: &'a [u8] = self.value_array;
+		self.value_range(index).map(|a| &array[a])
+	}
+
+	#[inline]
+	pub fn iter<'b>(&'b self) -> impl Iterator<Item = &'a [u8]> + 'b {
+		let array: &'a [u8] = self.value_array;
+		self.ends.windows(2).map(move |a| &array[a[0] .. a[1]])
+	}
+}
@@ -16,6 +16,8 @@ pub type LamanshValueSized = LamanshU64;
 pub type U8 = LamanshU8;
 ///65535 len
 pub type U16 = LamanshU16;
+///16777215 len
+pub type U24 = LamanshU24;
 ///4294967295 len
 pub type U32 = LamanshU32;
 ///18446744073709551615 len
@@ -84,6 +86,39 @@ pub trait LamanshSized: Clone + Debug + Eq + Hash + Ord + PartialEq + PartialOrd
 	
 	#[inline(always)]
 	fn max_value() -> usize;
+
+	///Runtime description of the type
+	#[inline(always)]
+	fn width() -> LamanshWidth;
+
+	///Append the ends of the values for all the headers of `array` in one pass,
+	///the end of the last value is returned. `None` - the sum does not fit into `usize`.
+	#[inline]
+	fn read_ends(array: &[u8], result: &mut Vec<usize>) -> Option<usize> {
+		read_ends_with(array, Self::byted(), Self::max_value(), result, Self::read_usize)
+	}
+}
+
+//The width is a constant after inlining, so the loop without the checks is vectorized.
+#[inline(always)]
+fn read_ends_with<F: Fn(&[u8]) -> usize>(array: &[u8], byted: usize, max: usize, result: &mut Vec<usize>, read: F) -> Option<usize> {
+	let len = array.len() / byted;
+	let array = &array[.. len * byted];
+	result.reserve(len);
+
+	let mut end = 0usize;
+	match len.checked_mul(max) {
+		Some(_) => result.extend(array.chunks(byted).map(|a| {
+			end += read(a);
+			end
+		})),
+		None => for a in array.chunks(byted) {
+			end = end.checked_add(read(a))?;
+			result.push(end);
+		},
+	}
+
+	Some( end )
 }
 
 impl LamanshSized for LamanshU8 {
@@ -123,6 +158,11 @@ impl LamanshSized for LamanshU8 {
 	fn max_value() -> usize {
 		u8::max_value() as usize
 	}
+	
+	#[inline(always)]
+	fn width() -> LamanshWidth {
+		LamanshWidth::U8
+	}
 }
 
 impl LamanshSized for LamanshU16 {
@@ -159,6 +199,11 @@ impl LamanshSized for LamanshU16 {
 	fn max_value() -> usize {
 		u16::max_value() as usize
 	}
+	
+	#[inline(always)]
+	fn width() -> LamanshWidth {
+		LamanshWidth::U16
+	}
 }
 
 impl LamanshSized for LamanshU24 {
@@ -193,7 +238,12 @@ impl LamanshSized for LamanshU24 {
 	
 	#[inline(always)]
 	fn max_value() -> usize {
-		u32::max_value() as usize
+		0x00FF_FFFF
+	}
+	
+	#[inline(always)]
+	fn width() -> LamanshWidth {
+		LamanshWidth::U24
 	}
 }
 
@@ -231,6 +281,11 @@ impl LamanshSized for LamanshU32 {
 	fn max_value() -> usize {
 		u32::max_value() as usize
 	}
+	
+	#[inline(always)]
+	fn width() -> LamanshWidth {
+		LamanshWidth::U32
+	}
 }
 
 impl LamanshSized for LamanshU64 {
@@ -268,5 +323,89 @@ impl LamanshSized for LamanshU64 {
 	fn max_value() -> usize {
 		u64::max_value() as usize
 	}
+	
+	#[inline(always)]
+	fn width() -> LamanshWidth {
+		LamanshWidth::U64
+	}
 }
 
+
+///Width of a name or value, selected at runtime.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LamanshWidth {
+	U8,
+	U16,
+	U24,
+	U32,
+	U64,
+}
+
+impl LamanshWidth {
+	#[inline]
+	pub fn from_byted(byted: usize) -> Option<Self> {
+		match byted {
+			1 => Some( LamanshWidth::U8 ),
+			2 => Some( LamanshWidth::U16 ),
+			3 => Some( LamanshWidth::U24 ),
+			4 => Some( LamanshWidth::U32 ),
+			8 => Some( LamanshWidth::U64 ),
+			_ => None,
+		}
+	}
+
+	#[inline]
+	pub fn byted(&self) -> usize {
+		match *self {
+			LamanshWidth::U8 => LamanshU8::byted(),
+			LamanshWidth::U16 => LamanshU16::byted(),
+			LamanshWidth::U24 => LamanshU24::byted(),
+			LamanshWidth::U32 => LamanshU32::byted(),
+			LamanshWidth::U64 => LamanshU64::byted(),
+		}
+	}
+
+	#[inline]
+	pub fn read_usize(&self, array: &[u8]) -> usize {
+		match *self {
+			LamanshWidth::U8 => LamanshU8::read_usize(array),
+			LamanshWidth::U16 => LamanshU16::read_usize(array),
+			LamanshWidth::U24 => LamanshU24::read_usize(array),
+			LamanshWidth::U32 => LamanshU32::read_usize(array),
+			LamanshWidth::U64 => LamanshU64::read_usize(array),
+		}
+	}
+
+	#[inline]
+	pub fn write_usize(&self, num: usize, array: &mut [u8]) {
+		match *self {
+			LamanshWidth::U8 => LamanshU8::write_usize(num, array),
+			LamanshWidth::U16 => LamanshU16::write_usize(num, array),
+			LamanshWidth::U24 => LamanshU24::write_usize(num, array),
+			LamanshWidth::U32 => LamanshU32::write_usize(num, array),
+			LamanshWidth::U64 => LamanshU64::write_usize(num, array),
+		}
+	}
+
+	///The smallest width that holds the number
+	#[inline]
+	pub fn narrowest(num: usize) -> Self {
+		for a in [LamanshWidth::U8, LamanshWidth::U16, LamanshWidth::U24, LamanshWidth::U32].iter() {
+			if num <= a.max_value() {
+				return *a;
+			}
+		}
+		LamanshWidth::U64
+	}
+
+	#[inline]
+	pub fn max_value(&self) -> usize {
+		match *self {
+			LamanshWidth::U8 => LamanshU8::max_value(),
+			LamanshWidth::U16 => LamanshU16::max_value(),
+			LamanshWidth::U24 => LamanshU24::max_value(),
+			LamanshWidth::U32 => LamanshU32::max_value(),
+			LamanshWidth::U64 => LamanshU64::max_value(),
+		}
+	}
+}
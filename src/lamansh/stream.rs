@@ -0,0 +1,252 @@
+/*!
+Many messages in one byte stream.
+
+`LamanshFraming::Prefixed` - every message is preceded by its len (4, Big Endian).
+`LamanshFraming::Implicit` - messages follow each other, the len is calculated from the headers.
+ */
+
+extern crate byteorder;
+use self::byteorder::BigEndian;
+use self::byteorder::ByteOrder;
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::ops::Range;
+use lamansh::sized::LamanshSized;
+use lamansh::cluLamansh;
+use lamansh::cluLamanshErr;
+use lamansh::invalid_data;
+use lamansh::header_len;
+
+///Len of the outer prefix of `LamanshFraming::Prefixed`
+pub const LAMANSH_STREAM_PREFIX_LEN: usize = 4;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LamanshFraming {
+	Prefixed,
+	Implicit,
+}
+
+///Len of the message according to its headers.
+pub fn message_len<NC: LamanshSized + 'static, N: LamanshSized + 'static>(array: &[u8]) -> Result<usize, cluLamanshErr> {
+	let n = header_len::<NC, N>(array)?;
+
+	let mut len_n = n;
+	for a in array[NC::byted() .. n].chunks(N::byted()) {
+		len_n = match len_n.checked_add(N::read_usize(a)) {
+			Some(a) => a,
+			_ => return Err( cluLamanshErr::ErrGetNextValue ),
+		};
+	}
+	if len_n > array.len() {
+		return Err( cluLamanshErr::ErrGetNextValue );
+	}
+
+	Ok( len_n )
+}
+
+
+#[derive(Debug)]
+pub struct LamanshStreamWriter<W: Write, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	write: W,
+	framing: LamanshFraming,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<W: Write, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshStreamWriter<W, NC, N> {
+	#[inline]
+	pub fn new(write: W, framing: LamanshFraming) -> Self {
+		Self {
+			write: write,
+			framing: framing,
+
+			_n_count_phantom: PhantomData,
+			_n_phantom: PhantomData,
+		}
+	}
+
+	///Write the message, it must not contain bytes after the values.
+	pub fn write_message(&mut self, lamansh: &[u8]) -> io::Result<()> {
+		if message_len::<NC, N>(lamansh).map_err(invalid_data)? != lamansh.len() {
+			return Err( io::Error::new(io::ErrorKind::InvalidInput, "bytes after the values of the message") );
+		}
+
+		if let LamanshFraming::Prefixed = self.framing {
+			if lamansh.len() > u32::max_value() as usize {
+				return Err( io::Error::new(io::ErrorKind::InvalidInput, "the message does not fit into the u32 len") );
+			}
+			let mut prefix = [0u8; LAMANSH_STREAM_PREFIX_LEN];
+			BigEndian::write_u32(&mut prefix, lamansh.len() as u32);
+			self.write.write_all(&prefix)?;
+		}
+		self.write.write_all(lamansh)
+	}
+
+	#[inline]
+	pub fn into_inner(self) -> W {
+		self.write
+	}
+}
+
+
+///Messages of the stream in memory and their ranges (without the outer prefix).
+#[derive(Debug, Clone)]
+pub struct LamanshStreamIter<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	array: &'a [u8],
+	n: usize,
+	framing: LamanshFraming,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshStreamIter<'a, NC, N> {
+	#[inline]
+	pub fn new(array: &'a [u8], framing: LamanshFraming) -> Self {
+		Self {
+			array: array,
+			n: 0,
+			framing: framing,
+
+			_n_count_phantom: PhantomData,
+			_n_phantom: PhantomData,
+		}
+	}
+
+	fn next_range(&self) -> Result<Range<usize>, cluLamanshErr> {
+		let array = &self.array[self.n ..];
+		match self.framing {
+			LamanshFraming::Prefixed => {
+				let len = match array.get(.. LAMANSH_STREAM_PREFIX_LEN) {
+					Some(a) => BigEndian::read_u32(a) as usize,
+					_ => return Err( cluLamanshErr::ErrGetSizeArray ),
+				};
+				let start = self.n + LAMANSH_STREAM_PREFIX_LEN;
+				if self.array.len() - start < len {
+					return Err( cluLamanshErr::ErrGetValue );
+				}
+				Ok( start .. start + len )
+			},
+			LamanshFraming::Implicit => {
+				let len = message_len::<NC, N>(array)?;
+				Ok( self.n .. self.n + len )
+			},
+		}
+	}
+}
+
+impl<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static> Iterator for LamanshStreamIter<'a, NC, N> {
+	type Item = Result<(Range<usize>, cluLamansh<'a, NC, N>), cluLamanshErr>;
+
+	fn next(&mut self) -> Option< Self::Item > {
+		if self.n >= self.array.len() {
+			return None;
+		}
+
+		let range = match self.next_range() {
+			Ok(a) => a,
+			Err(e) => {
+				self.n = self.array.len();
+				return Some( Err( e ) );
+			},
+		};
+		self.n = range.end;
+
+		let array: &'a [u8] = self.array;
+		Some( cluLamansh::new(&array[range.clone()]).map(|a| (range, a)) )
+	}
+}
+
+
+///Messages of the stream from `Read`, the buffer of the message is reused.
+#[derive(Debug)]
+pub struct LamanshStreamReader<R: Read, NC: LamanshSized + 'static, N: LamanshSized + 'static> {
+	read: R,
+	n: u64,
+	framing: LamanshFraming,
+
+	buffer: Vec<u8>,
+
+	_n_count_phantom: PhantomData<NC>,
+	_n_phantom: PhantomData<N>,
+}
+
+impl<R: Read, NC: LamanshSized + 'static, N: LamanshSized + 'static> LamanshStreamReader<R, NC, N> {
+	#[inline]
+	pub fn new(read: R, framing: LamanshFraming) -> Self {
+		Self {
+			read: read,
+			n: 0,
+			framing: framing,
+
+			buffer: Vec::new(),
+
+			_n_count_phantom: PhantomData,
+			_n_phantom: PhantomData,
+		}
+	}
+
+	///Read exactly `len` bytes to the end of the buffer, `Ok(false)` - the stream ended before the first byte
+	fn read_buffer(&mut self, len: usize) -> io::Result<bool> {
+		let start = self.buffer.len();
+		(&mut self.read).take(len as u64).read_to_end(&mut self.buffer)?;
+
+		let readed = self.buffer.len() - start;
+		self.n += readed as u64;
+		match readed {
+			a if a == len => Ok( true ),
+			0 if start == 0 => Ok( false ),
+			_ => Err( io::Error::new(io::ErrorKind::UnexpectedEof, "the stream ended inside the message") ),
+		}
+	}
+
+	///The next message and its range, `None` - the stream ended.
+	pub fn next_message(&mut self) -> Option<io::Result<(Range<u64>, cluLamansh<NC, N>)>> {
+		match self.read_message() {
+			Ok(Some(range)) => Some( cluLamansh::new(&self.buffer).map(|a| (range, a)).map_err(invalid_data) ),
+			Ok(None) => None,
+			Err(e) => Some( Err( e ) ),
+		}
+	}
+
+	fn read_message(&mut self) -> io::Result<Option<Range<u64>>> {
+		self.buffer.clear();
+
+		if let LamanshFraming::Prefixed = self.framing {
+			if !self.read_buffer(LAMANSH_STREAM_PREFIX_LEN)? {
+				return Ok( None );
+			}
+			let len = BigEndian::read_u32(&self.buffer) as usize;
+			self.buffer.clear();
+
+			let start = self.n;
+			self.read_buffer(len)?;
+			return Ok( Some( start .. self.n ) );
+		}
+
+		let start = self.n;
+		if !self.read_buffer(NC::byted())? {
+			return Ok( None );
+		}
+		let head_len = match (NC::read_usize(&self.buffer) as u64).checked_add(1).and_then(|a| a.checked_mul(N::byted() as u64)) {
+			Some(a) if a <= usize::max_value() as u64 => a as usize,
+			_ => return Err( invalid_data(cluLamanshErr::ErrGetValueHead) ),
+		};
+		self.read_buffer(head_len)?;
+
+		let mut len_n = 0usize;
+		for a in self.buffer[NC::byted() ..].chunks(N::byted()) {
+			len_n = match len_n.checked_add(N::read_usize(a)) {
+				Some(a) => a,
+				_ => return Err( invalid_data(cluLamanshErr::ErrGetNextValue) ),
+			};
+		}
+		self.read_buffer(len_n)?;
+
+		Ok( Some( start .. self.n ) )
+	}
+}
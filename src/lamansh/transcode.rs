@@ -0,0 +1,93 @@
+/*! Changing the profile (NC/N) of the message without decoding the values. */
+
+use lamansh::buffer::LamanshBuffer;
+use lamansh::build::ToLamanshErr;
+use lamansh::sized::LamanshSized;
+use lamansh::sized::LamanshWidth;
+use lamansh::cluLamansh;
+use lamansh::header_len;
+
+///Rewrite the headers for the new profile, the values are copied as is. Bytes after the values are an error.
+pub fn transcode<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static, NC2: LamanshSized + 'static, N2: LamanshSized + 'static>(lamansh: &cluLamansh<'a, NC, N>) -> Result<Vec<u8>, ToLamanshErr> {
+	let len_elements = lamansh.len();
+	if len_elements > NC2::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+	let len_n = match lamansh.values_len() {
+		Some(a) if a == lamansh.value_array.len() => a,
+		_ => return Err( ToLamanshErr::InvalidLamansh ),
+	};
+
+	let n_c_sized = NC2::byted();
+	let n_sized = N2::byted();
+	let mut vec_result = vec![0u8; n_c_sized + (n_sized * len_elements) + len_n];
+	NC2::write_usize(len_elements-1, &mut vec_result[.. n_c_sized]);
+
+	let mut n = n_c_sized;
+	for a in lamansh.value_head_array.chunks(N::byted()) {
+		let len = N::read_usize(a);
+		if len > N2::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+		N2::write_usize(len, &mut vec_result[n .. n + n_sized]);
+		n += n_sized;
+	}
+	vec_result[n .. ].copy_from_slice(lamansh.value_array);
+
+	Ok( vec_result )
+}
+
+///Rewrite the headers for the new profile in the buffer, the values are moved once. Bytes after the values are an error.
+pub fn transcode_buffer<NC: LamanshSized + 'static, N: LamanshSized + 'static, NC2: LamanshSized + 'static, N2: LamanshSized + 'static>(vec_result: &mut LamanshBuffer) -> Result<(), ToLamanshErr> {
+	let n = match header_len::<NC, N>(vec_result) {
+		Ok(a) => a,
+		_ => return Err( ToLamanshErr::InvalidLamansh ),
+	};
+	let lens: Vec<usize> = vec_result[NC::byted() .. n].chunks(N::byted()).map(N::read_usize).collect();
+
+	let len_elements = lens.len();
+	if len_elements > NC2::max_value() {
+		return Err( ToLamanshErr::CountOverflow );
+	}
+	let mut len_n = 0usize;
+	for len in lens.iter() {
+		if *len > N2::max_value() {
+			return Err( ToLamanshErr::ValueOverflow );
+		}
+		len_n = match len_n.checked_add(*len) {
+			Some(a) => a,
+			_ => return Err( ToLamanshErr::InvalidLamansh ),
+		};
+	}
+	if len_n != vec_result.len() - n {
+		return Err( ToLamanshErr::InvalidLamansh );
+	}
+
+	let n_c_sized = NC2::byted();
+	let n_sized = N2::byted();
+	let new_n = n_c_sized + (n_sized * len_elements);
+	let end = vec_result.len();
+
+	if new_n > n {
+		vec_result.resize(end + new_n - n, 0);
+	}
+	vec_result.copy_within(n .. end, new_n);
+	if new_n < n {
+		vec_result.truncate(end + new_n - n);
+	}
+
+	NC2::write_usize(len_elements-1, &mut vec_result[.. n_c_sized]);
+	for (i, len) in lens.into_iter().enumerate() {
+		let a = n_c_sized + n_sized * i;
+		N2::write_usize(len, &mut vec_result[a .. a + n_sized]);
+	}
+
+	Ok( () )
+}
+
+///The smallest profile (NC, N) that holds the message.
+pub fn narrowest_profile<'a, NC: LamanshSized + 'static, N: LamanshSized + 'static>(lamansh: &cluLamansh<'a, NC, N>) -> (LamanshWidth, LamanshWidth) {
+	let max_len = lamansh.value_head_array.chunks(N::byted()).map(N::read_usize).max().unwrap_or(0);
+
+	(LamanshWidth::narrowest(lamansh.len()), LamanshWidth::narrowest(max_len))
+}
@@ -111,11 +111,14 @@ pub fn main() {
 */
 
 pub mod lamansh;
+pub mod ffi;
+mod dyn_chunks;
 
 use lamansh::sized::LamanshNameCountSized;
 use lamansh::sized::LamanshValueSized;
 use lamansh::cluLamansh;
 use lamansh::cluLamanshErr;
+use lamansh::envelope::LamanshEnveloped;
 
 use lamansh::sized::U8;
 use lamansh::sized::LamanshSized;
@@ -142,6 +145,12 @@ pub fn new_lamansh_mini<'a>(array: &'a [u8]) -> Result< cluLamansh<'a, U8, U8> ,
 	new_custom_lamansh(array)
 }
 
+///Create a handler from the received data with the envelope. Sized name and value are taken from the envelope
+#[inline]
+pub fn new_lamansh_enveloped<'a>(array: &'a [u8]) -> Result< LamanshEnveloped<'a>, cluLamanshErr > {
+	LamanshEnveloped::new(array)
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -152,6 +161,7 @@ mod tests {
 	use lamansh::sized::U8;
 	use lamansh::sized::U16;
 	use new_custom_lamansh;
+	use lamansh::sized::U32;
 	use lamansh::sized::U64;
 	use lamansh::build::ToLamansh;
 	use test::Bencher;
@@ -160,6 +170,62 @@ mod tests {
 	use lamansh::buffer::LamanshBuffer;
 	use lamansh::array::LamanshVecArray;
 	use lamansh::array::LamanshSliceArray;
+	use lamansh::schema::LamanshSchema;
+	use lamansh::schema::LamanshConstraint;
+	use lamansh::schema::LamanshValueType;
+	use lamansh::schema::LamanshViolation;
+	use lamansh::schema::LamanshViolationKind;
+	use new_lamansh_enveloped;
+	use lamansh::cluLamansh;
+	use lamansh::cluLamanshErr;
+	use lamansh::sized::LamanshSized;
+	use lamansh::envelope::LamanshEnvelope;
+	use lamansh::envelope::LamanshVisitor;
+	use lamansh::checksum::crc32c;
+	use lamansh::envelope::LAMANSH_FLAG_CHECKSUM;
+	use lamansh::mutable::cluLamanshMut;
+	use lamansh::editor::LamanshEditor;
+	use lamansh::concat::concat;
+	use lamansh::sized::U24;
+	use lamansh::sized::LamanshWidth;
+	use lamansh::transcode::transcode;
+	use lamansh::transcode::transcode_buffer;
+	use lamansh::transcode::narrowest_profile;
+	use lamansh::log::LamanshLog;
+	use lamansh::log::LamanshLogIter;
+	use lamansh::stream::LamanshFraming;
+	use lamansh::stream::LamanshStreamIter;
+	use lamansh::stream::LamanshStreamReader;
+	use lamansh::stream::LamanshStreamWriter;
+	use lamansh::file::LamanshFile;
+	use lamansh::indexed::cluLamanshIndexed;
+	use lamansh::indexed::to_lamansh_indexed;
+	use lamansh::indexed::indexed_from_lamansh;
+	use lamansh::envelope::LAMANSH_FLAG_INDEXED;
+	use lamansh::dump::LamanshDump;
+	use lamansh::json::LamanshJson;
+	use lamansh::json::LamanshJsonBinary;
+	use lamansh::json::LamanshJsonErr;
+	use lamansh::json::to_json;
+	use lamansh::json::from_json;
+	use lamansh::json::LAMANSH_JSON_DEPTH;
+	use std::borrow::Cow;
+	use lamansh::codec::LamanshCodec;
+	use lamansh::codec::LamanshLz;
+	use lamansh::codec::LamanshIdentity;
+	use lamansh::codec::to_lamansh_codec;
+	use lamansh::auth::LamanshAuthenticator;
+	use lamansh::auth::LamanshHmacSha256;
+	use lamansh::auth::constant_time_eq;
+	use lamansh::auth::sha256;
+	use lamansh::dedup::cluLamanshDedup;
+	use lamansh::dedup::to_lamansh_dedup;
+	use lamansh::envelope::LAMANSH_FLAG_DEDUP;
+	use lamansh::delta::apply_delta;
+	use lamansh::delta::encode_delta;
+	use lamansh::scratch::LamanshDecodeScratch;
+	use lamansh::scratch::parse_into;
+	use lamansh::pool::LamanshBufferPool;
 
 	#[bench]
 	fn build_u8_u8_lamansh(b: &mut Bencher) {
@@ -293,6 +359,37 @@ mod tests {
 		});
 	}
 
+	fn small_values() -> Vec<u8> {
+		let values = (0 .. 20000u32).map(|a| vec![a as u8; (a % 5) as usize]).collect::<Vec<_>>();
+		(&values[..]).to_lamansh::<U16, U16>().unwrap()
+	}
+
+	#[bench]
+	fn run_lamansh_small_values(b: &mut Bencher) {
+		let array = small_values();
+		let lamansh = new_custom_lamansh::<U16, U16>(&array).unwrap();
+
+		b.iter(|| {
+			let mut len = 0;
+			for a in lamansh.iter() {
+				len += a.unwrap().len();
+			}
+			len
+		});
+	}
+
+	#[bench]
+	fn offsets_lamansh_small_values(b: &mut Bencher) {
+		let array = small_values();
+		let lamansh = new_custom_lamansh::<U16, U16>(&array).unwrap();
+		let mut table = Vec::new();
+
+		b.iter(|| {
+			lamansh.offsets_into(&mut table).unwrap();
+			table[table.len() - 1]
+		});
+	}
+
 	#[test]
 	fn err_to_empty_test() {
 		let array: &[&[u8]] = &[];
@@ -452,4 +549,869 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn schema_validate() {
+		let schema = LamanshSchema::range(2, 3)
+			.with(0, LamanshConstraint::Type(LamanshValueType::U32))
+			.with(1, LamanshConstraint::Utf8)
+			.with(1, LamanshConstraint::MaxLen(4))
+			.with(2, LamanshConstraint::FixedLen(2));
+
+		let array = [&[0u8, 0, 0, 1][..], &b"test"[..], &b"12"[..]].to_lamansh::<U8, U8>().unwrap();
+		assert_eq!(schema.validate(&new_custom_lamansh::<U8, U8>(&array).unwrap()), Ok( () ));
+
+		let array = [&[0u8, 1][..], &b"\xFFtest"[..], &b"1"[..], &b""[..]].to_lamansh::<U8, U8>().unwrap();
+		assert_eq!(
+			schema.validate(&new_custom_lamansh::<U8, U8>(&array).unwrap()),
+
+			Err(vec![
+				LamanshViolation { index: Some(0), kind: LamanshViolationKind::Type(LamanshValueType::U32) },
+				LamanshViolation { index: Some(1), kind: LamanshViolationKind::Utf8 },
+				LamanshViolation { index: Some(1), kind: LamanshViolationKind::MaxLen(4, 5) },
+				LamanshViolation { index: Some(2), kind: LamanshViolationKind::FixedLen(2, 1) },
+				LamanshViolation { index: None, kind: LamanshViolationKind::Count(4) },
+			])
+		);
+	}
+
+	#[test]
+	fn schema_validate_array() {
+		let schema = LamanshSchema::exact(2).with(1, LamanshConstraint::Type(LamanshValueType::Bool));
+
+		assert_eq!(schema.validate_array(&[&b"a"[..], &[1u8][..]]), Ok( () ));
+		assert_eq!(
+			schema.validate_array(&[vec![2u8]]),
+
+			Err(vec![
+				LamanshViolation { index: None, kind: LamanshViolationKind::Count(1) },
+			])
+		);
+	}
+
+	#[test]
+	fn buffer_set_len() {
+		//len < capacity, the new len is past the capacity
+		let mut buffer = LamanshBuffer::with_capacity(8);
+		buffer.set_len(4);
+		buffer.set_len(100);
+		assert_eq!(buffer.len(), 100);
+		assert!(buffer.capacity() >= 100);
+		for a in buffer.iter_mut() {
+			*a = 0xFF;
+		}
+
+		//the new len is equal to the capacity
+		let mut buffer = LamanshBuffer::with_capacity(8);
+		buffer.set_len(8);
+		assert_eq!(buffer.len(), 8);
+
+		buffer.set_len(2);
+		assert_eq!(buffer.len(), 2);
+	}
+
+	#[test]
+	fn envelope_profile() {
+		struct Count;
+		impl<'a> LamanshVisitor<'a> for Count {
+			type Output = (usize, usize);
+
+			fn visit<NC: LamanshSized + 'static, N: LamanshSized + 'static>(self, lamansh: cluLamansh<'a, NC, N>) -> (usize, usize) {
+				(N::byted(), lamansh.into_iter().count())
+			}
+		}
+
+		let body = [&b"TEST"[..], &b"1"[..], &b""[..]].to_lamansh::<U8, U16>().unwrap();
+		let array = LamanshEnvelope::new::<U8, U16>().wrap(&body);
+		assert_eq!(&array[.. 7], &[b'L', b'M', b'S', b'H', 1, 0x12, 0][..]);
+
+		let enveloped = new_lamansh_enveloped(&array).unwrap();
+		assert_eq!(enveloped.as_slice(), &body[..]);
+		assert_eq!(enveloped.visit(Count), Ok( (2, 3) ));
+		assert_eq!(enveloped.lamansh::<U8, U64>(), Err( cluLamanshErr::ErrEnvelopeProfile ));
+		assert_eq!(enveloped.lamansh::<U8, U16>().unwrap().into_iter().count(), 3);
+	}
+
+	#[test]
+	fn envelope_errors() {
+		let mut array = LamanshEnvelope::new::<U8, U8>().wrap(&[0, 0]);
+
+		assert_eq!(new_lamansh_enveloped(&array[.. 6]), Err( cluLamanshErr::ErrGetEnvelope ));
+		array[6] = 0x80;
+		assert_eq!(new_lamansh_enveloped(&array), Err( cluLamanshErr::ErrEnvelopeFlags ));
+		array[5] = 0x15;
+		assert_eq!(new_lamansh_enveloped(&array), Err( cluLamanshErr::ErrEnvelopeProfile ));
+		array[4] = 2;
+		assert_eq!(new_lamansh_enveloped(&array), Err( cluLamanshErr::ErrEnvelopeVersion ));
+		array[0] = 0;
+		assert_eq!(new_lamansh_enveloped(&array), Err( cluLamanshErr::ErrEnvelopeMagic ));
+	}
+
+	#[test]
+	fn checksum_crc32c() {
+		assert_eq!(crc32c(b"123456789"), 0xE3069283);
+		assert_eq!(crc32c(b""), 0);
+	}
+
+	#[test]
+	fn checksum_trailer() {
+		let array = [&b"TEST"[..], &b"12"[..]].to_lamansh_checksum::<U8, U16>().unwrap();
+		let body = [&b"TEST"[..], &b"12"[..]].to_lamansh::<U8, U16>().unwrap();
+		assert_eq!(&array[.. body.len()], &body[..]);
+		assert_eq!(array.len(), body.len() + 4);
+
+		let lamansh = cluLamansh::<U8, U16>::new_checksum(&array).unwrap();
+		assert_eq!(lamansh.into_iter().collect::<Vec<_>>(), vec![Ok( &b"TEST"[..] ), Ok( &b"12"[..] )]);
+
+		let mut broken = array.clone();
+		broken[0] ^= 0x04;
+		match cluLamansh::<U8, U16>::new_checksum(&broken) {
+			Err( cluLamanshErr::ChecksumMismatch { expected, actual } ) => {
+				assert_eq!(expected, crc32c(&body));
+				assert_ne!(actual, expected);
+			},
+			a => panic!("{:?}", a),
+		}
+		assert_eq!(cluLamansh::<U8, U16>::new_checksum(&array[.. 3]), Err( cluLamanshErr::ErrGetChecksum ));
+
+		let enveloped = LamanshEnvelope::new::<U8, U16>().with_flags(LAMANSH_FLAG_CHECKSUM).wrap(&array);
+		assert_eq!(new_lamansh_enveloped(&enveloped).unwrap().as_slice(), &body[..]);
+	}
+
+	#[test]
+	fn mut_overwrite() {
+		let mut array = [&b"time"[..], &b""[..], &b"seq"[..]].to_lamansh::<U8, U16>().unwrap();
+		{
+			let mut lamansh = cluLamanshMut::<U8, U16>::new(&mut array).unwrap();
+			assert_eq!(lamansh.len(), 3);
+
+			assert_eq!(lamansh.overwrite(0, b"TIME"), Ok( () ));
+			assert_eq!(lamansh.overwrite(2, b"SEQ2"), Err( cluLamanshErr::ErrOverwriteLen ));
+			assert_eq!(lamansh.overwrite(3, b""), Err( cluLamanshErr::ErrGetIndex ));
+
+			lamansh.get_mut(2).unwrap()[0] = b'S';
+			for a in lamansh.iter_mut() {
+				a.reverse();
+			}
+		}
+
+		assert_eq!(array, [&b"EMIT"[..], &b""[..], &b"qeS"[..]].to_lamansh::<U8, U16>().unwrap());
+
+		let len = array.len();
+		assert_eq!(cluLamanshMut::<U8, U16>::new(&mut array[.. len-1]), Err( cluLamanshErr::ErrGetNextValue ));
+	}
+
+	#[test]
+	fn editor_splice() {
+		let mut buffer = LamanshBuffer::new();
+		[&b"a"[..], &b"bb"[..], &b"ccc"[..], &b"dddd"[..]].update_buffer::<U8, U16>(&mut buffer).unwrap();
+		{
+			let mut editor = LamanshEditor::<U8, U16>::new(&mut buffer).unwrap();
+
+			assert_eq!(editor.insert(1, b"new"), Ok( () ));
+			assert_eq!(editor.remove(3), Ok( () ));
+			assert_eq!(editor.replace(0, b"AAAAA"), Ok( () ));
+			assert_eq!(editor.replace(1, b""), Ok( () ));
+			assert_eq!(editor.push(b"end"), Ok( () ));
+			assert_eq!(editor.swap(4, 1), Ok( () ));
+			assert_eq!(editor.len(), 5);
+		}
+
+		assert_eq!(
+			&buffer[..],
+
+			&[&b"AAAAA"[..], &b"end"[..], &b"bb"[..], &b"dddd"[..], &b""[..]].to_lamansh::<U8, U16>().unwrap()[..]
+		);
+	}
+
+	#[test]
+	fn editor_errors() {
+		let mut array = vec![&b"12"[..]; u8::max_value() as usize].to_lamansh::<U8, U8>().unwrap();
+		let mut editor = LamanshEditor::<U8, U8>::new(&mut array).unwrap();
+
+		assert_eq!(editor.push(b""), Err( ToLamanshErr::CountOverflow ));
+		assert_eq!(editor.replace(0, &[0u8; 256]), Err( ToLamanshErr::ValueOverflow ));
+		assert_eq!(editor.swap(0, 255), Err( ToLamanshErr::IndexOverflow ));
+
+		let mut array = [&b"1"[..]].to_lamansh::<U8, U8>().unwrap();
+		let mut editor = LamanshEditor::<U8, U8>::new(&mut array).unwrap();
+		assert_eq!(editor.remove(0), Err( ToLamanshErr::EmptyArray ));
+	}
+
+	#[test]
+	fn concat_messages() {
+		let a = [&b"1"[..], &b"22"[..]].to_lamansh::<U8, U16>().unwrap();
+		let b = [&b""[..], &b"333"[..], &b"4444"[..]].to_lamansh::<U8, U16>().unwrap();
+		let expected = [&b"1"[..], &b"22"[..], &b""[..], &b"333"[..], &b"4444"[..]].to_lamansh::<U8, U16>().unwrap();
+
+		let lamansh_a = new_custom_lamansh::<U8, U16>(&a).unwrap();
+		let lamansh_b = new_custom_lamansh::<U8, U16>(&b).unwrap();
+		assert_eq!(concat(&[lamansh_a.clone(), lamansh_b.clone()]), Ok( expected.clone() ));
+
+		let mut buffer = LamanshBuffer::new();
+		assert_eq!(buffer.append_message(&lamansh_a), Ok( () ));
+		assert_eq!(buffer.append_message(&lamansh_b), Ok( () ));
+		assert_eq!(&buffer[..], &expected[..]);
+
+		//bytes after the values of the buffer
+		let mut trailing = a.clone();
+		trailing.push(0xFF);
+		let mut buffer = LamanshBuffer::array(trailing.clone());
+		assert_eq!(buffer.append_message(&lamansh_b), Err( ToLamanshErr::InvalidLamansh ));
+		assert_eq!(&buffer[..], &trailing[..]);
+	}
+
+	#[test]
+	fn concat_count_overflow() {
+		let a = vec![&b"1"[..]; 200].to_lamansh::<U8, U8>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U8>(&a).unwrap();
+		assert_eq!(concat(&[lamansh.clone(), lamansh.clone()]), Err( ToLamanshErr::CountOverflow ));
+
+		let mut buffer = LamanshBuffer::array(a.clone());
+		assert_eq!(buffer.append_message(&lamansh), Err( ToLamanshErr::CountOverflow ));
+	}
+
+	#[test]
+	fn transcode_profile() {
+		let values = [&b"test"[..], &b""[..], &[7u8; 300][..]];
+		let a = values.to_lamansh::<U8, U64>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U64>(&a).unwrap();
+
+		assert_eq!(narrowest_profile(&lamansh), (LamanshWidth::U8, LamanshWidth::U16));
+		assert_eq!(transcode::<U8, U64, U16, U16>(&lamansh), values.to_lamansh::<U16, U16>());
+		assert_eq!(transcode::<U8, U64, U8, U8>(&lamansh), Err( ToLamanshErr::ValueOverflow ));
+
+		let mut buffer = LamanshBuffer::array(a.clone());
+		assert_eq!(transcode_buffer::<U8, U64, U16, U24>(&mut buffer), Ok( () ));
+		assert_eq!(&buffer[..], &values.to_lamansh::<U16, U24>().unwrap()[..]);
+		assert_eq!(transcode_buffer::<U16, U24, U8, U64>(&mut buffer), Ok( () ));
+		assert_eq!(&buffer[..], &a[..]);
+
+		//bytes after the values
+		let mut trailing = a.clone();
+		trailing.push(0xFF);
+		let lamansh = new_custom_lamansh::<U8, U64>(&trailing).unwrap();
+		assert_eq!(transcode::<U8, U64, U16, U16>(&lamansh), Err( ToLamanshErr::InvalidLamansh ));
+		let mut buffer = LamanshBuffer::array(trailing.clone());
+		assert_eq!(transcode_buffer::<U8, U64, U16, U16>(&mut buffer), Err( ToLamanshErr::InvalidLamansh ));
+		assert_eq!(&buffer[..], &trailing[..]);
+	}
+
+	fn temp_path(name: &str) -> ::std::path::PathBuf {
+		let path = ::std::env::temp_dir().join(format!("cluLamansh_{}_{}", ::std::process::id(), name));
+		let _e = ::std::fs::remove_file(&path);
+		path
+	}
+
+	#[test]
+	fn log_append_read() {
+		let path = temp_path("log_append_read");
+		{
+			let mut log = LamanshLog::open(&path).unwrap();
+			log.append(&[&b"1"[..], &b"22"[..]].to_lamansh::<U8, U16>().unwrap()).unwrap();
+			log.append(&[&b"333"[..]].to_lamansh::<U8, U16>().unwrap()).unwrap();
+		}
+
+		let mut log = LamanshLog::open(&path).unwrap();
+		log.append(&[&b""[..], &b"4444"[..]].to_lamansh::<U8, U16>().unwrap()).unwrap();
+
+		let array = log.read().unwrap();
+		let records: Vec<Vec<&[u8]>> = LamanshLogIter::<U8, U16>::new(&array)
+			.map(|a| a.unwrap().into_iter().map(|a| a.unwrap()).collect())
+			.collect();
+		assert_eq!(records, vec![vec![&b"1"[..], &b"22"[..]], vec![&b"333"[..]], vec![&b""[..], &b"4444"[..]]]);
+
+		::std::fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn log_torn_tail() {
+		use std::io::Write;
+
+		let path = temp_path("log_torn_tail");
+		let record = [&b"record"[..]].to_lamansh::<U8, U8>().unwrap();
+		let len = {
+			let mut log = LamanshLog::open(&path).unwrap();
+			log.append(&record).unwrap();
+			log.len()
+		};
+		{
+			let mut file = ::std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+			file.write_all(&[0, 0, 0, 9, 1, 2]).unwrap();
+		}
+
+		let mut log = LamanshLog::open(&path).unwrap();
+		assert_eq!(log.len(), len);
+		assert_eq!(::std::fs::metadata(&path).unwrap().len(), len);
+
+		log.append(&record).unwrap();
+		let array = log.read().unwrap();
+		assert_eq!(LamanshLogIter::<U8, U8>::new(&array).filter(|a| a.is_ok()).count(), 2);
+
+		//the damaged last record is a torn write
+		let len = log.len();
+		drop(log);
+		{
+			let mut file = ::std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+			file.write_all(&[0, 0, 0, 1, 7, 0, 0, 0, 0]).unwrap();
+		}
+		assert_eq!(LamanshLog::open(&path).unwrap().len(), len);
+		assert_eq!(::std::fs::metadata(&path).unwrap().len(), len);
+
+		::std::fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn log_damaged_middle() {
+		let path = temp_path("log_damaged_middle");
+		let record = [&b"record"[..]].to_lamansh::<U8, U8>().unwrap();
+		{
+			let mut log = LamanshLog::open(&path).unwrap();
+			for _ in 0 .. 3 {
+				log.append(&record).unwrap();
+			}
+		}
+
+		let mut array = ::std::fs::read(&path).unwrap();
+		let frame = array.len() / 3;
+		array[frame + 6] ^= 1;
+		::std::fs::write(&path, &array).unwrap();
+
+		let err = LamanshLog::open(&path).unwrap_err();
+		assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+		assert_eq!(::std::fs::read(&path).unwrap(), array);
+
+		//the len of the middle record points past the end of the file
+		array[frame + 6] ^= 1;
+		array[frame + 2] ^= 1;
+		::std::fs::write(&path, &array).unwrap();
+
+		let err = LamanshLog::open(&path).unwrap_err();
+		assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+		assert_eq!(::std::fs::metadata(&path).unwrap().len(), array.len() as u64);
+		assert_eq!(::std::fs::read(&path).unwrap(), array);
+
+		::std::fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn stream_framing() {
+		let one = [&b"1"[..], &b"22"[..]].to_lamansh::<U8, U16>().unwrap();
+		let two = [&b""[..]].to_lamansh::<U8, U16>().unwrap();
+
+		for &framing in [LamanshFraming::Prefixed, LamanshFraming::Implicit].iter() {
+			let mut writer = LamanshStreamWriter::<_, U8, U16>::new(Vec::new(), framing);
+			writer.write_message(&one).unwrap();
+			writer.write_message(&two).unwrap();
+			let array = writer.into_inner();
+
+			let ranges: Vec<_> = LamanshStreamIter::<U8, U16>::new(&array, framing)
+				.map(|a| a.unwrap())
+				.map(|(range, a)| {
+					assert_eq!(a.len(), if range.len() == one.len() { 2 } else { 1 });
+					range
+				})
+				.collect();
+			assert_eq!(ranges.len(), 2);
+			assert_eq!(&array[ranges[0].clone()], &one[..]);
+			assert_eq!(&array[ranges[1].clone()], &two[..]);
+
+			let mut reader = LamanshStreamReader::<_, U8, U16>::new(&array[..], framing);
+			{
+				let (range, a) = reader.next_message().unwrap().unwrap();
+				assert_eq!(range, ranges[0].start as u64 .. ranges[0].end as u64);
+				assert_eq!(a.iter().map(|a| a.unwrap()).collect::<Vec<_>>(), vec![&b"1"[..], &b"22"[..]]);
+			}
+			assert_eq!(reader.next_message().unwrap().unwrap().0, ranges[1].start as u64 .. ranges[1].end as u64);
+			assert!(reader.next_message().is_none());
+		}
+	}
+
+	#[test]
+	fn stream_errors() {
+		let one = [&b"value"[..]].to_lamansh::<U8, U16>().unwrap();
+
+		let mut writer = LamanshStreamWriter::<_, U8, U16>::new(Vec::new(), LamanshFraming::Implicit);
+		let mut trailing = one.clone();
+		trailing.push(0);
+		assert!(writer.write_message(&trailing).is_err());
+		writer.write_message(&one).unwrap();
+
+		let mut array = writer.into_inner();
+		array.extend_from_slice(&one[.. one.len() - 1]);
+
+		let mut iter = LamanshStreamIter::<U8, U16>::new(&array, LamanshFraming::Implicit);
+		assert!(iter.next().unwrap().is_ok());
+		assert!(iter.next().unwrap().is_err());
+		assert!(iter.next().is_none());
+
+		let mut reader = LamanshStreamReader::<_, U8, U16>::new(&array[..], LamanshFraming::Implicit);
+		assert!(reader.next_message().unwrap().is_ok());
+		assert_eq!(reader.next_message().unwrap().err().unwrap().kind(), ::std::io::ErrorKind::UnexpectedEof);
+
+		let array = [0xFF; 8];
+		let mut reader = LamanshStreamReader::<_, U64, U16>::new(&array[..], LamanshFraming::Implicit);
+		assert_eq!(reader.next_message().unwrap().err().unwrap().kind(), ::std::io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn file_random_access() {
+		use std::io::Cursor;
+		use std::io::Read;
+
+		let body = [&b"first"[..], &b""[..], &b"third value"[..]].to_lamansh::<U8, U16>().unwrap();
+		let mut array = b"skip".to_vec();
+		array.extend_from_slice(&body);
+
+		let mut cursor = Cursor::new(array);
+		cursor.set_position(4);
+		let mut file = LamanshFile::<_, U8, U16>::new(cursor).unwrap();
+		assert_eq!(file.len(), 3);
+		assert_eq!(file.value_len(2), Some(11));
+		assert_eq!(file.value_len(3), None);
+
+		assert_eq!(file.read_value(2).unwrap(), b"third value".to_vec());
+		assert_eq!(file.read_value(0).unwrap(), b"first".to_vec());
+		assert_eq!(file.read_value(1).unwrap(), b"".to_vec());
+		assert!(file.read_value(3).is_err());
+
+		let mut value = [0u8; 5];
+		file.value_reader(2).unwrap().read_exact(&mut value).unwrap();
+		assert_eq!(&value, b"third");
+
+		let mut cut = body.clone();
+		cut.pop();
+		assert!(LamanshFile::<_, U8, U16>::new(Cursor::new(cut)).is_err());
+
+		let err = LamanshFile::<_, U64, U16>::new(Cursor::new(vec![0xFF; 8])).unwrap_err();
+		assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn indexed_layout() {
+		let values = [&b"ab"[..], &b""[..], &b"cde"[..]];
+		let array = to_lamansh_indexed::<U8, U16, _>(&values).unwrap();
+		assert_eq!(array, vec![2, 0, 2, 0, 2, 0, 5, b'a', b'b', b'c', b'd', b'e']);
+
+		let classic = values.to_lamansh::<U8, U16>().unwrap();
+		assert_eq!(indexed_from_lamansh(&new_custom_lamansh::<U8, U16>(&classic).unwrap()).unwrap(), array);
+
+		let indexed = cluLamanshIndexed::<U8, U16>::new(&array).unwrap();
+		assert_eq!(indexed.len(), 3);
+		assert_eq!(indexed.get(2), Some(&b"cde"[..]));
+		assert_eq!(indexed.get(3), None);
+		assert_eq!(indexed.value_range(1), Some(2 .. 2));
+		assert_eq!(indexed.iter().collect::<Vec<_>>(), values.to_vec());
+		assert_eq!(indexed.to_lamansh().unwrap(), classic);
+
+		assert_eq!(indexed.index_of(0), Some(0));
+		assert_eq!(indexed.index_of(1), Some(0));
+		assert_eq!(indexed.index_of(2), Some(2));
+		assert_eq!(indexed.index_of(4), Some(2));
+		assert_eq!(indexed.index_of(5), None);
+
+		assert!(to_lamansh_indexed::<U8, U8, _>(&[&[0u8; 200][..], &[0u8; 100][..]]).is_err());
+		assert!(cluLamanshIndexed::<U8, U16>::new(&[1, 0, 2, 0, 1, b'a', b'b']).is_err());
+
+		let enveloped = LamanshEnvelope::new::<U8, U16>().with_flags(LAMANSH_FLAG_INDEXED).wrap(&array);
+		let enveloped = new_lamansh_enveloped(&enveloped).unwrap();
+		assert!(enveloped.lamansh::<U8, U16>().is_err());
+		assert_eq!(enveloped.lamansh_indexed::<U8, U16>().unwrap().get(0), Some(&b"ab"[..]));
+	}
+
+	#[test]
+	fn range_views() {
+		let array = [&b"0"[..], &b"11"[..], &b""[..], &b"333"[..], &b"4"[..]].to_lamansh::<U8, U16>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+
+		let range = lamansh.slice(1 .. 4).unwrap();
+		assert_eq!(range.len(), 3);
+		assert_eq!(range.iter().map(|a| a.unwrap()).collect::<Vec<_>>(), vec![&b"11"[..], &b""[..], &b"333"[..]]);
+		assert_eq!(range.get(2), Some( Ok( &b"333"[..] ) ));
+		assert_eq!(range.get(3), None);
+
+		assert_eq!(lamansh.slice(5 ..).unwrap().len(), 0);
+		assert_eq!(lamansh.slice(..= 4).unwrap().len(), 5);
+		assert_eq!(lamansh.slice(3 .. 6), Err( cluLamanshErr::ErrGetIndex ));
+		assert_eq!(lamansh.slice(..= usize::max_value()), Err( cluLamanshErr::ErrGetIndex ));
+		assert_eq!(lamansh.slice((::std::ops::Bound::Excluded(usize::max_value()), ::std::ops::Bound::Unbounded)), Err( cluLamanshErr::ErrGetIndex ));
+
+		let (a, b) = lamansh.split_at(2).unwrap();
+		assert_eq!(a.iter().map(|a| a.unwrap()).collect::<Vec<_>>(), vec![&b"0"[..], &b"11"[..]]);
+		assert_eq!(b.get(0), Some( Ok( &b""[..] ) ));
+		assert!(lamansh.split_at(6).is_err());
+
+		assert_eq!(lamansh.select(&[3, 0, 3]).unwrap(), [&b"333"[..], &b"0"[..], &b"333"[..]].to_lamansh::<U8, U16>().unwrap());
+		assert_eq!(lamansh.select(&[5]), Err( ToLamanshErr::IndexOverflow ));
+		assert_eq!(lamansh.select(&[]), Err( ToLamanshErr::EmptyArray ));
+	}
+
+	#[test]
+	fn dump_format() {
+		let array = [&b"hi"[..], &b""[..], &b"long\n"[..]].to_lamansh::<U8, U16>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+
+		assert_eq!(
+			LamanshDump::new(&lamansh).with_limit(3).to_string(),
+			"00000000  count     02  (3 elements)\n\
+			00000001  len[0]    00 02  (2)\n\
+			00000003  len[1]    00 00  (0)\n\
+			00000005  len[2]    00 05  (5)\n\
+			00000007  value[0]  68 69  \"hi\"\n\
+			00000009  value[1]    \"\"\n\
+			00000009  value[2]  6c 6f 6e  \"lon\" ... (5 bytes)\n"
+		);
+
+		assert_eq!(format!("{:#?}", lamansh), "[\n    b\"hi\",\n    b\"\",\n    b\"long\\n\",\n]");
+		assert!(format!("{:?}", lamansh).starts_with("cluLamansh { array: [2, 0, 2,"));
+	}
+
+	#[test]
+	fn json_round_trip() {
+		let map = [&b"name"[..], &b"lamansh"[..], &b"data"[..], &[1u8, 2, 3][..]].to_lamansh::<U8, U16>().unwrap();
+		let list = [&b"a"[..], &b"b"[..], &b"a"[..]].to_lamansh::<U8, U16>().unwrap();
+		let array = [
+			&b"text \"quoted\"\n"[..], &b""[..], &[0xFFu8, 0x00, 0x10, 0x20][..], &map[..], &list[..], "\u{1F600}".as_bytes(),
+		].to_lamansh::<U8, U16>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+
+		let json = to_json(&lamansh).unwrap();
+		assert_eq!(json, "[\"text \\\"quoted\\\"\\n\",\"\",{\"base64\":\"/wAQIA==\"},\
+			{\"map\":{\"name\":\"lamansh\",\"data\":{\"base64\":\"AQID\"}}},\
+			{\"lamansh\":[\"a\",\"b\",\"a\"]},\"\u{1F600}\"]");
+		assert_eq!(from_json::<U8, U16>(&json).unwrap(), array);
+
+		let json = LamanshJson::new().with_binary(LamanshJsonBinary::Hex).with_nested(false).to_json(&lamansh).unwrap();
+		assert!(json.contains("{\"hex\":\"ff001020\"}"));
+		assert!(!json.contains("map"));
+		assert_eq!(from_json::<U8, U16>(&json).unwrap(), array);
+
+		assert_eq!(
+			from_json::<U8, U16>(" [ \"\\ud83d\\ude00\\u0041\" , { \"hex\" : \"00\" } ] ").unwrap(),
+			["\u{1F600}A".as_bytes(), &[0u8][..]].to_lamansh::<U8, U16>().unwrap()
+		);
+
+		//parses as a message of 256 elements, which can not be built
+		let mut value = vec![0xFFu8];
+		value.extend_from_slice(&[0; 512]);
+		let array = [&value[..]].to_lamansh::<U8, U16>().unwrap();
+		let json = to_json(&new_custom_lamansh::<U8, U16>(&array).unwrap()).unwrap();
+		assert!(json.contains("base64"));
+		assert_eq!(from_json::<U8, U16>(&json).unwrap(), array);
+
+		//messages nested deeper than the limit of the parser are written as binary values
+		let mut array = [&[0xFFu8][..]].to_lamansh::<U8, U16>().unwrap();
+		for _ in 0 .. 20 {
+			array = [&array[..]].to_lamansh::<U8, U16>().unwrap();
+			let json = to_json(&new_custom_lamansh::<U8, U16>(&array).unwrap()).unwrap();
+			assert_eq!(from_json::<U8, U16>(&json).unwrap(), array);
+		}
+		let json = to_json(&new_custom_lamansh::<U8, U16>(&array).unwrap()).unwrap();
+		assert_eq!(json.matches("{\"lamansh\":").count(), LAMANSH_JSON_DEPTH / 2 - 2);
+	}
+
+	#[test]
+	fn json_errors() {
+		assert_eq!(from_json::<U8, U16>("[\"a\""), Err( LamanshJsonErr::Syntax(4) ));
+		assert_eq!(from_json::<U8, U16>("[\"a\"] x"), Err( LamanshJsonErr::Syntax(6) ));
+		assert_eq!(from_json::<U8, U16>("[1]"), Err( LamanshJsonErr::Element ));
+		assert_eq!(from_json::<U8, U16>("{}"), Err( LamanshJsonErr::Element ));
+		assert_eq!(from_json::<U8, U16>("[{\"base64\":\"AB==\"}]"), Err( LamanshJsonErr::Binary ));
+		assert_eq!(from_json::<U8, U16>("[{\"hex\":\"0\"}]"), Err( LamanshJsonErr::Binary ));
+		assert_eq!(from_json::<U8, U16>("[]"), Err( LamanshJsonErr::Build(ToLamanshErr::EmptyArray) ));
+		assert_eq!(from_json::<U8, U16>(&"[".repeat(100)), Err( LamanshJsonErr::Depth ));
+	}
+
+	#[test]
+	fn codec_lz() {
+		let mut random = Vec::with_capacity(5000);
+		let mut seed = 0x1234_5678u32;
+		for _ in 0 .. 5000 {
+			seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+			random.push((seed >> 16) as u8);
+		}
+		let text = "lamansh protocol, ".repeat(300).into_bytes();
+		let mut mixed = random[.. 300].to_vec();
+		mixed.extend_from_slice(&text[.. 1000]);
+		mixed.extend_from_slice(&[0u8; 700]);
+
+		for a in [&b""[..], &b"abc"[..], &b"aaaaaaaa"[..], &random[..], &text[..], &mixed[..]].iter() {
+			let encoded = LamanshLz.encode(a);
+			assert_eq!(LamanshLz.decode(&encoded).unwrap(), a.to_vec());
+		}
+		assert!(LamanshLz.encode(&text).len() < text.len() / 10);
+
+		let encoded = LamanshLz.encode(&text);
+		assert_eq!(LamanshLz.decode(&encoded[.. encoded.len() - 1]), Err( cluLamanshErr::ErrCodec ));
+		assert_eq!(LamanshLz.decode(&[10, 0x00, 1, 2]), Err( cluLamanshErr::ErrCodec ));
+		assert_eq!(LamanshLz.decode(&[8, 0x10, b'a', 0, 2]), Err( cluLamanshErr::ErrCodec ));
+	}
+
+	#[test]
+	fn codec_values() {
+		let text = "value ".repeat(50);
+		let array = to_lamansh_codec::<U8, U16, _, _>(&LamanshLz, &[text.as_bytes(), &b"id"[..], &b""[..]]).unwrap();
+		let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+		assert_eq!(lamansh.len(), 3);
+
+		let values: Vec<_> = lamansh.iter_codec(&LamanshLz).map(|a| a.unwrap()).collect();
+		assert_eq!(values, vec![Cow::Borrowed(text.as_bytes()), Cow::Borrowed(&b"id"[..]), Cow::Borrowed(&b""[..])]);
+		match (&values[0], &values[1]) {
+			(&Cow::Owned(_), &Cow::Borrowed(_)) => {},
+			_ => panic!("only the encoded value is copied"),
+		}
+		assert_eq!(lamansh.iter_codec(&LamanshIdentity).next(), Some( Err( cluLamanshErr::ErrCodec ) ));
+
+		let body = [text.as_bytes(), &b"id"[..]].to_lamansh::<U8, U16>().unwrap();
+		let array = LamanshEnvelope::new::<U8, U16>().wrap_codec(&LamanshLz, &body);
+		assert!(array.len() < body.len());
+
+		let enveloped = new_lamansh_enveloped(&array).unwrap();
+		assert_eq!(enveloped.envelope().codec(), LamanshLz.id());
+		assert_eq!(enveloped.lamansh::<U8, U16>(), Err( cluLamanshErr::ErrEnvelopeFlags ));
+		assert_eq!(enveloped.decode_body(&LamanshIdentity), Err( cluLamanshErr::ErrCodec ));
+		assert_eq!(&*enveloped.decode_body(&LamanshLz).unwrap(), &body[..]);
+	}
+
+	#[test]
+	fn auth_sha256() {
+		fn hex(array: &[u8]) -> String {
+			array.iter().map(|a| format!("{:02x}", a)).collect()
+		}
+
+		assert_eq!(hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+		assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+		assert_eq!(
+			hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+			"248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+		);
+
+		//RFC 4231
+		assert_eq!(
+			hex(&LamanshHmacSha256::new(b"Jefe").tag(b"what do ya want for nothing?")),
+			"5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+		assert_eq!(
+			hex(&LamanshHmacSha256::new(&[0xAA; 131]).tag(b"Test Using Larger Than Block-Size Key - Hash Key First")),
+			"60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+		);
+
+		assert!(constant_time_eq(b"tag", b"tag"));
+		assert!(!constant_time_eq(b"tag", b"tab"));
+		assert!(!constant_time_eq(b"tag", b"tags"));
+	}
+
+	#[test]
+	fn auth_tag() {
+		let auth = LamanshHmacSha256::new(b"key");
+		let array = [&b"TEST"[..], &b""[..]].to_lamansh_auth::<U8, U16, _>(&auth).unwrap();
+		let body = [&b"TEST"[..], &b""[..]].to_lamansh::<U8, U16>().unwrap();
+		assert_eq!(&array[.. body.len()], &body[..]);
+		assert_eq!(&array[body.len() ..], &auth.sign(&body)[..]);
+
+		let lamansh = cluLamansh::<U8, U16>::new_auth(&array, &auth).unwrap();
+		assert_eq!(lamansh.iter().map(|a| a.unwrap()).collect::<Vec<_>>(), vec![&b"TEST"[..], &b""[..]]);
+
+		assert_eq!(cluLamansh::<U8, U16>::new_auth(&array, &LamanshHmacSha256::new(b"other")), Err( cluLamanshErr::ErrAuthTag ));
+		for i in 0 .. array.len() {
+			let mut damaged = array.clone();
+			damaged[i] ^= 0x80;
+			assert_eq!(cluLamansh::<U8, U16>::new_auth(&damaged, &auth), Err( cluLamanshErr::ErrAuthTag ));
+		}
+		assert_eq!(cluLamansh::<U8, U16>::new_auth(&array[.. 10], &auth), Err( cluLamanshErr::ErrGetTag ));
+	}
+
+	#[test]
+	fn dedup_references() {
+		let values = [&b"on"[..], &b""[..], &b"off"[..], &b"on"[..], &b""[..], &b"on"[..]];
+		let array = to_lamansh_dedup::<U8, U8, _>(&values).unwrap();
+		assert_eq!(array, vec![5, 2, 0, 3, 0x80, 0, 0x80, b'o', b'n', b'o', b'f', b'f']);
+
+		let lamansh = cluLamanshDedup::<U8, U8>::new(&array).unwrap();
+		assert_eq!(lamansh.len(), 6);
+		assert_eq!(lamansh.get(5), Some(&b"on"[..]));
+		assert_eq!(lamansh.get(6), None);
+		assert_eq!(lamansh.iter().collect::<Vec<_>>(), values.to_vec());
+		assert_eq!(lamansh.to_lamansh().unwrap(), values.to_lamansh::<U8, U8>().unwrap());
+
+		let stats = lamansh.stats();
+		assert_eq!(stats.references, 2);
+		assert_eq!(stats.stored_bytes, 5);
+		assert_eq!(stats.saved_bytes, 4);
+
+		//the reference to itself or a later element
+		assert_eq!(cluLamanshDedup::<U8, U8>::new(&[1, 0x81, 0]), Err( cluLamanshErr::ErrDedupRef ));
+		assert_eq!(cluLamanshDedup::<U8, U8>::new(&[1, 2, 0x80, b'o']), Err( cluLamanshErr::ErrGetNextValue ));
+		assert!(to_lamansh_dedup::<U8, U8, _>(&[&[0u8; 128][..]]).is_err());
+
+		let enveloped = LamanshEnvelope::new::<U8, U8>().with_flags(LAMANSH_FLAG_DEDUP).wrap(&array);
+		let enveloped = new_lamansh_enveloped(&enveloped).unwrap();
+		assert!(enveloped.lamansh::<U8, U8>().is_err());
+		assert_eq!(enveloped.lamansh_dedup::<U8, U8>().unwrap().get(3), Some(&b"on"[..]));
+	}
+
+	#[test]
+	fn delta_patch() {
+		let prev_values = (0 .. 200u8).map(|a| vec![a; 3]).collect::<Vec<_>>();
+		let prev_array = (&prev_values[..]).to_lamansh::<U8, U16>().unwrap();
+		let prev = new_custom_lamansh::<U8, U16>(&prev_array).unwrap();
+
+		let mut next = prev_values.clone();
+		next[7] = b"changed".to_vec();
+		next[150] = vec![];
+		let patch = encode_delta(&prev, &next).unwrap();
+		//hash, count, number of the changes, 2 * (index, len) and the values
+		assert_eq!(patch.len(), 8 + 1 + 1 + 2 * 3 + 7);
+		assert_eq!(apply_delta(&prev, &patch).unwrap(), (&next[..]).to_lamansh::<U8, U16>().unwrap());
+
+		//unchanged, shrunk and grown messages
+		let patch = encode_delta(&prev, &prev_values).unwrap();
+		assert_eq!(patch.len(), 10);
+		assert_eq!(apply_delta(&prev, &patch).unwrap(), prev_array);
+		let patch = encode_delta(&prev, &prev_values[.. 2]).unwrap();
+		assert_eq!(apply_delta(&prev, &patch).unwrap(), (&prev_values[.. 2]).to_lamansh::<U8, U16>().unwrap());
+		let mut grown = prev_values.clone();
+		grown.push(b"new".to_vec());
+		let patch = encode_delta(&prev, &grown).unwrap();
+		assert_eq!(apply_delta(&prev, &patch).unwrap(), (&grown[..]).to_lamansh::<U8, U16>().unwrap());
+
+		let other_array = (&next[..]).to_lamansh::<U8, U16>().unwrap();
+		let other = new_custom_lamansh::<U8, U16>(&other_array).unwrap();
+		assert_eq!(apply_delta(&other, &patch), Err( cluLamanshErr::ErrDeltaBase ));
+		assert_eq!(apply_delta(&prev, &patch[.. patch.len() - 1]), Err( cluLamanshErr::ErrDeltaPatch ));
+		assert_eq!(apply_delta(&prev, &patch[.. 4]), Err( cluLamanshErr::ErrDeltaPatch ));
+
+		//forged counts of the patch
+		let base_array = [&b"a"[..]].to_lamansh::<U64, U16>().unwrap();
+		let base = new_custom_lamansh::<U64, U16>(&base_array).unwrap();
+		let mut forged = encode_delta(&base, &[&b"a"[..]]).unwrap();
+		assert_eq!(forged.len(), 24);
+		forged[8 .. 16].copy_from_slice(&[0xFF; 8]);
+		assert_eq!(apply_delta(&base, &forged), Err( cluLamanshErr::ErrDeltaPatch ));
+		forged[8 .. 16].copy_from_slice(&[0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+		assert_eq!(apply_delta(&base, &forged), Err( cluLamanshErr::ErrDeltaPatch ));
+		//both counts, the elements fit into the existing and the changed elements
+		forged[8 .. 16].copy_from_slice(&[0x0F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]);
+		forged[16 .. 24].copy_from_slice(&[0x10, 0, 0, 0, 0, 0, 0, 0]);
+		assert_eq!(apply_delta(&base, &forged), Err( cluLamanshErr::ErrDeltaPatch ));
+		forged[16 .. 24].copy_from_slice(&[0x0F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+		assert_eq!(apply_delta(&base, &forged), Err( cluLamanshErr::ErrDeltaPatch ));
+	}
+
+	#[test]
+	fn offsets_table() {
+		let values = [&b"ab"[..], &b""[..], &b"cde"[..]];
+		let array = values.to_lamansh::<U8, U16>().unwrap();
+		let lamansh = new_custom_lamansh::<U8, U16>(&array).unwrap();
+
+		let offsets = lamansh.offsets().unwrap();
+		assert_eq!(offsets.as_table(), &[0, 2, 2, 5]);
+		assert_eq!(offsets.len(), 3);
+		assert_eq!(offsets.value_range(2), Some(2 .. 5));
+		assert_eq!(offsets.get(3), None);
+		assert_eq!(offsets.iter().collect::<Vec<_>>(), values.to_vec());
+
+		//all the widths agree with the iterator
+		let values = (0 .. 300u32).map(|a| vec![a as u8; (a % 7) as usize]).collect::<Vec<_>>();
+		fn check<N: LamanshSized + 'static>(values: &[Vec<u8>]) {
+			let array = values.to_lamansh::<U16, N>().unwrap();
+			let lamansh = new_custom_lamansh::<U16, N>(&array).unwrap();
+			let offsets = lamansh.offsets().unwrap();
+			assert!(offsets.iter().eq(lamansh.iter().map(|a| a.unwrap())));
+		}
+		check::<U8>(&values);
+		check::<U16>(&values);
+		check::<U24>(&values);
+		check::<U32>(&values);
+		check::<U64>(&values);
+
+		let mut table = vec![7; 10];
+		assert_eq!(new_custom_lamansh::<U8, U8>(&[1, 1, 2, b'a', b'b']).unwrap().offsets_into(&mut table), Err( cluLamanshErr::ErrGetNextValue ));
+		assert!(new_custom_lamansh::<U8, U64>(&[1, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 1]).unwrap().offsets().is_err());
+	}
+
+	#[cfg(feature = "parallel")]
+	#[test]
+	fn parallel_build() {
+		use lamansh::parallel::to_lamansh_parallel;
+		use lamansh::parallel::update_buffer_parallel;
+
+		let values = (0 .. 5000u32).map(|a| vec![a as u8; (a % 300) as usize]).collect::<Vec<_>>();
+		assert_eq!(to_lamansh_parallel::<U16, U16, _>(&values).unwrap(), (&values[..]).to_lamansh::<U16, U16>().unwrap());
+
+		let mut buffer = LamanshBuffer::array(vec![1; 100000]);
+		update_buffer_parallel::<U16, U32, _>(&values[.. 10], &mut buffer).unwrap();
+		assert_eq!(&buffer[..], &(&values[.. 10]).to_lamansh::<U16, U32>().unwrap()[..]);
+
+		let empty: &[&[u8]] = &[];
+		assert_eq!(to_lamansh_parallel::<U8, U8, _>(empty), Err( ToLamanshErr::EmptyArray ));
+		assert_eq!(to_lamansh_parallel::<U8, U16, _>(&values), Err( ToLamanshErr::CountOverflow ));
+		assert_eq!(to_lamansh_parallel::<U16, U8, _>(&values), Err( ToLamanshErr::ValueOverflow ));
+	}
+
+	#[test]
+	fn decode_scratch() {
+		let mut scratch = LamanshDecodeScratch::with_capacity(16);
+		let capacity = scratch.capacity();
+
+		let values = [&b"ab"[..], &b""[..], &b"cde"[..]];
+		let array = values.to_lamansh::<U8, U16>().unwrap();
+		{
+			let view = parse_into::<U8, U16>(&mut scratch, &array).unwrap();
+			assert_eq!(view.len(), 3);
+			assert_eq!(view.value_range(2), Some(2 .. 5));
+			assert_eq!(view.get(2), Some(&b"cde"[..]));
+			assert_eq!(view.get(3), None);
+			assert_eq!(view.iter().collect::<Vec<_>>(), values.to_vec());
+		}
+
+		let array = [&b"x"[..]].to_lamansh::<U8, U16>().unwrap();
+		assert_eq!(scratch.parse::<U8, U16>(&array).unwrap().iter().collect::<Vec<_>>(), vec![&b"x"[..]]);
+		assert_eq!(scratch.capacity(), capacity);
+
+		assert_eq!(scratch.parse::<U8, U8>(&[1, 1, 2, b'a', b'b']).err(), Some( cluLamanshErr::ErrGetNextValue ));
+		assert_eq!(scratch.parse::<U8, U8>(&[]).err(), Some( cluLamanshErr::ErrGetSizeArray ));
+	}
+
+	#[test]
+	fn buffer_pool() {
+		let pool = LamanshBufferPool::new().with_max_len(2).with_shrink_capacity(64);
+		{
+			let mut a = pool.get();
+			let b = pool.get();
+			let c = pool.get();
+			a.extend_from_slice(&[0; 1000]);
+			drop((a, b, c));
+		}
+		let stats = pool.stats();
+		assert_eq!((stats.hits, stats.misses, stats.pooled), (0, 3, 2));
+
+		//the buffers are returned empty and shrunk
+		let a = pool.get();
+		let b = pool.get();
+		assert!(a.is_empty() && b.is_empty());
+		assert!(a.capacity() <= 64 && b.capacity() <= 64);
+		let _owned = a.into_inner();
+		drop(b);
+		let stats = pool.stats();
+		assert_eq!((stats.hits, stats.misses, stats.pooled), (2, 3, 1));
+
+		let pool = ::std::sync::Arc::new(LamanshBufferPool::new());
+		let threads = (0 .. 4).map(|i| {
+			let pool = pool.clone();
+			::std::thread::spawn(move || {
+				for _ in 0 .. 100 {
+					let mut buffer = pool.get();
+					[&[i as u8][..]].update_buffer::<U8, U8>(&mut buffer).unwrap();
+					assert_eq!(&buffer[..], &[0, 1, i as u8]);
+				}
+			})
+		}).collect::<Vec<_>>();
+		for a in threads {
+			a.join().unwrap();
+		}
+		let stats = pool.stats();
+		assert_eq!(stats.hits + stats.misses, 400);
+		assert!(stats.misses <= 4 && stats.pooled == stats.misses);
+	}
+
 }
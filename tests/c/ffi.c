@@ -0,0 +1,40 @@
+#include <stdio.h>
+#include <string.h>
+#include "cluLamansh.h"
+
+#define CHECK(a) do { if (!(a)) { fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #a); return 1; } } while (0)
+
+int main(void) {
+	const uint8_t *data[3] = { (const uint8_t *)"TEST", NULL, (const uint8_t *)"value" };
+	size_t lens[3] = { 4, 0, 5 };
+
+	uint8_t *array = NULL;
+	size_t len = 0;
+	CHECK(lamansh_build(1, 2, data, lens, 3, &array, &len) == LAMANSH_OK);
+
+	const uint8_t expected[] = { 2, 0, 4, 0, 0, 0, 5, 'T', 'E', 'S', 'T', 'v', 'a', 'l', 'u', 'e' };
+	CHECK(len == sizeof(expected));
+	CHECK(memcmp(array, expected, len) == 0);
+
+	LamanshHandle *handle = NULL;
+	CHECK(lamansh_parse(array, len, 1, 2, &handle) == LAMANSH_OK);
+	CHECK(lamansh_count(handle) == 3);
+
+	const uint8_t *value = NULL;
+	size_t value_len = 0;
+	CHECK(lamansh_get(handle, 2, &value, &value_len) == LAMANSH_OK);
+	CHECK(value_len == 5 && memcmp(value, "value", 5) == 0);
+	CHECK(lamansh_get(handle, 1, &value, &value_len) == LAMANSH_OK && value_len == 0);
+	CHECK(lamansh_get(handle, 3, &value, &value_len) == LAMANSH_ERR_GET_INDEX);
+	lamansh_free(handle);
+
+	CHECK(lamansh_parse(array, len - 1, 1, 2, &handle) == LAMANSH_ERR_GET_NEXT_VALUE);
+	CHECK(lamansh_parse(array, len, 1, 5, &handle) == LAMANSH_ERR_WIDTH);
+	lamansh_buffer_free(array, len);
+
+	CHECK(lamansh_build(1, 2, data, lens, 0, &array, &len) == LAMANSH_ERR_EMPTY_ARRAY);
+	lens[2] = 256;
+	CHECK(lamansh_build(1, 1, data, lens, 3, &array, &len) == LAMANSH_ERR_VALUE_OVERFLOW);
+
+	return 0;
+}
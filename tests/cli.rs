@@ -0,0 +1,64 @@
+//! The `lamansh` binary: encode, then decode, inspect, validate and transcode the file.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+fn lamansh(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_lamansh")).args(args).output().unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+	env::temp_dir().join(format!("lamansh_cli_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn cli_round_trip() {
+	let message = temp_path("message");
+	let wide = temp_path("wide");
+	let message_s = message.to_str().unwrap();
+	let wide_s = wide.to_str().unwrap();
+
+	let output = lamansh(&["encode", "--count-width", "u8", "--len-width", "u16", "-o", message_s, "first", "second"]);
+	assert!(output.status.success());
+	assert_eq!(fs::read(&message).unwrap(), b"\x01\x00\x05\x00\x06firstsecond".to_vec());
+
+	let output = lamansh(&["decode", "--count-width", "u8", "--len-width", "u16", message_s]);
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), "first\nsecond\n");
+	let output = lamansh(&["decode", "--count-width", "u8", "--len-width", "u16", "--hex", message_s]);
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), "6669727374\n7365636f6e64\n");
+
+	let output = lamansh(&["inspect", "--count-width", "u8", "--len-width", "u16", message_s]);
+	let inspect = String::from_utf8(output.stdout).unwrap();
+	assert!(inspect.contains("elements: 2\n"));
+	assert!(inspect.contains("[1] offset 10 len 6\n"));
+
+	let output = lamansh(&["validate", "--count-width", "u8", "--len-width", "u16", message_s]);
+	assert!(output.status.success());
+	let output = lamansh(&["validate", "--count-width", "u16", "--len-width", "u16", message_s]);
+	assert!(!output.status.success());
+
+	let output = lamansh(&["transcode", "--count-width", "u8", "--len-width", "u16", "--to-count-width", "u16", "--to-len-width", "u32", "-o", wide_s, message_s]);
+	assert!(output.status.success());
+	let output = lamansh(&["decode", "--count-width", "u16", "--len-width", "u32", wide_s]);
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), "first\nsecond\n");
+
+	let _e = fs::remove_file(&message);
+	let _e = fs::remove_file(&wide);
+}
+
+#[test]
+fn cli_errors() {
+	let output = lamansh(&[]);
+	assert_eq!(output.status.code(), Some(2));
+
+	let output = lamansh(&["compress"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8(output.stderr).unwrap().starts_with("usage:"));
+
+	let output = lamansh(&["encode", "--len-width", "u7", "value"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(String::from_utf8(output.stderr).unwrap(), "unknown width: u7\n");
+}
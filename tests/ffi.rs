@@ -0,0 +1,37 @@
+//! The C test `tests/c/ffi.c` linked with the static library.
+
+extern crate cc;
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+#[cfg(unix)]
+fn c_api() {
+	let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+	let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+	let out = env::temp_dir().join(format!("lamansh_ffi_{}", std::process::id()));
+
+	//passed by `build.rs` from the build environment
+	let compiler = cc::Build::new()
+		.cargo_metadata(false)
+		.opt_level(0)
+		.host(env!("LAMANSH_HOST"))
+		.target(env!("LAMANSH_TARGET"))
+		.get_compiler();
+
+	let mut command = compiler.to_command();
+	command.arg(manifest.join("tests/c/ffi.c"))
+		.arg("-I").arg(manifest.join("include"))
+		.arg(deps.join("libcluLamansh.a"))
+		.arg("-o").arg(&out);
+	if cfg!(target_os = "linux") {
+		command.args(&["-lpthread", "-ldl", "-lm"]);
+	}
+	assert!(command.status().unwrap().success(), "failed to compile the C test");
+
+	let status = Command::new(&out).status().unwrap();
+	let _ = std::fs::remove_file(&out);
+	assert!(status.success(), "the C test failed");
+}